@@ -16,17 +16,52 @@ pub(crate) const INTERNAL_ERR: &str =
 
 /// Auto load the views from the given directory.
 ///
-/// Folder names should start with an alphabet and end with alphanumeric
-/// with underscores being allowed in the middle.
-///
-/// File names should start with an alphabet and end with alphanumeric
-/// with underscores being allowed in the middle. The only allowed
-/// extension is `.html`.
+/// Folder and file names should start with an alphabet and end with
+/// alphanumeric with underscores, or hyphens when `case` is `"kebab"`, being
+/// allowed in the middle. File names also need the extension given by the
+/// `extension` option, which is `.html` by default.
 ///
 /// Ignores the other files and folders which do not adhere the above rules.
 ///
-/// Both the folder and file names are converted to lower case before
-/// building the template.
+/// Each folder becomes a module and each file becomes a view named after the
+/// file in pascal case, like `UserCard` for `user_card.html`.
+///
+/// # Options
+///
+/// The paths can be followed by the options below, given as `name = value`.
+///
+/// * `module`: Name of the generated module. Defaults to `views`.
+/// * `root`: Path of the module in which the macro is called, used by the
+///   views to refer to the components. Defaults to `crate`.
+/// * `extension`: Extension of the template files. Defaults to `"html"`.
+/// * `style_const`: Whether to generate the `STYLE` and `SCRIPT` constants
+///   containing the styles and scripts of all the templates. Defaults to `true`.
+/// * `case`: Case of the file and folder names, either `"snake"` or `"kebab"`.
+///   Hyphens in the folder names are replaced by underscores in the module
+///   names. Defaults to `"snake"`.
+/// * `hot_reload`: Whether the views read their template from disk and
+///   interpret it on every render in debug builds, so that changes to the
+///   templates show up without recompiling. Needs the `hot-reload` feature
+///   of `reign`. See `reign::view::hot` for the supported templates.
+///   Defaults to `false`.
+/// * `escaper`: Path of a value implementing `reign::view::Escaper`, usually
+///   a unit struct, which escapes the values written by the views. Use
+///   `reign::view::XmlEscaper` for XML documents or `reign::view::NoEscaper`
+///   for plain text. Defaults to `reign::view::HtmlEscaper`.
+/// * `filters`: Path of a value implementing `reign::view::Filters`, usually
+///   a unit struct, whose methods are the filters used in the templates with
+///   `{{ value | filter }}`. Defaults to `reign::view::StdFilters`.
+/// * `locales`: Folder, relative to the crate, of the `<locale>.toml` message
+///   catalogs used by `{{ t!("key", name = value) }}`. Every message needs to
+///   exist in every locale and be given the arguments it uses. The catalogs are
+///   also available as the `CATALOG` static of the generated module.
+/// * `default_locale`: Locale used when the locale of the render context has no
+///   catalog, see `reign::view::i18n`. Defaults to `"en"`. The locale is the
+///   one given to `reign::view::Render::render_in`, or set with
+///   `reign::view::i18n::with_locale` for the other methods.
+/// * `minify`: Whether to collapse the whitespace of the templates to a single
+///   space, except inside `<pre>`, `<textarea>`, `<script>` and `<style>`, and
+///   drop their comments other than conditional comments. Defaults to `false`.
 ///
 /// # Examples
 ///
@@ -35,6 +70,14 @@ pub(crate) const INTERNAL_ERR: &str =
 ///
 /// views!("src", "views");
 /// ```
+///
+/// ```ignore
+/// mod ui {
+///     use reign::prelude::*;
+///
+///     views!("src", "ui", module = pages, root = crate::ui, case = "kebab");
+/// }
+/// ```
 #[cfg(feature = "view")]
 #[proc_macro]
 pub fn views(input: TokenStream) -> TokenStream {
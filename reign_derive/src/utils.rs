@@ -11,7 +11,6 @@ pub struct Options {
 }
 
 impl Options {
-    #[allow(unused)]
     pub fn remove(&mut self, key: &'static str) -> Option<Expr> {
         self.inner.remove(&Ident::new(key, Span::call_site()))
     }
@@ -28,6 +27,12 @@ impl Parse for Options {
 
         while !input.is_empty() {
             input.parse::<Comma>()?;
+
+            // Allow a trailing comma
+            if input.is_empty() {
+                break;
+            }
+
             let ident: Ident = input.parse()?;
             input.parse::<Eq>()?;
 
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use reign_view_ast::{Element, ItemTemplate, Node};
+
+use super::tokenize::{key, Manifest};
+
+/// Dependency graph of the views with an edge from every view
+/// to each of the components used in its template.
+pub struct Graph {
+    edges: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    pub fn new(views: &[(&str, &ItemTemplate)]) -> Self {
+        let index: HashMap<&str, usize> = views
+            .iter()
+            .enumerate()
+            .map(|(i, (key, _))| (*key, i))
+            .collect();
+
+        let edges = views
+            .iter()
+            .map(|(_, template)| {
+                let mut deps = vec![];
+
+                for component in components(&template.children) {
+                    let path = component.component_path().expect(crate::INTERNAL_ERR);
+
+                    if let Some(i) = index.get(key(&path).as_str()) {
+                        if !deps.contains(i) {
+                            deps.push(*i);
+                        }
+                    }
+                }
+
+                deps
+            })
+            .collect();
+
+        Graph { edges }
+    }
+
+    /// Indices of the views ordered such that every view comes after the
+    /// components it uses. Views which are part of a cycle are ordered
+    /// arbitrarily amongst themselves.
+    pub fn order(&self) -> Vec<usize> {
+        let mut visited = vec![false; self.edges.len()];
+        let mut order = vec![];
+
+        for i in 0..self.edges.len() {
+            self.visit(i, &mut visited, &mut order);
+        }
+
+        order
+    }
+
+    fn visit(&self, i: usize, visited: &mut Vec<bool>, order: &mut Vec<usize>) {
+        if visited[i] {
+            return;
+        }
+
+        visited[i] = true;
+
+        for dep in &self.edges[i] {
+            self.visit(*dep, visited, order);
+        }
+
+        order.push(i);
+    }
+}
+
+/// Checks that every component used in the template exists and
+/// is given all of its required fields and nothing else.
+///
+/// Components given `:..="props"` take the missing fields from it, and
+/// components forwarding their attributes with `!inherit-attrs` accept any other.
+///
+/// Errors are returned along with the position of the component element.
+pub fn check(template: &ItemTemplate, manifest: &Manifest) -> Vec<(usize, String)> {
+    let mut errors = vec![];
+
+    for component in components(&template.children) {
+        let path = component.component_path().expect(crate::INTERNAL_ERR);
+
+        let fields = match manifest.get(&path) {
+            Some(fields) => fields,
+            None => {
+                errors.push((
+                    component.pos,
+                    format!("unknown component `<{}>`", component.name),
+                ));
+                continue;
+            }
+        };
+
+        let props = component.component_props();
+
+        for prop in &props {
+            if !fields.contains(prop) && !fields.fallthrough {
+                errors.push((
+                    component.pos,
+                    format!("component `<{}>` has no prop `{}`", component.name, prop),
+                ));
+            }
+        }
+
+        for (field, _) in fields.keys() {
+            if !props.contains(&field) && !fields.is_optional(&field) && !component.has_spread() {
+                errors.push((
+                    component.pos,
+                    format!(
+                        "component `<{}>` is missing the required prop `{}`",
+                        component.name, field
+                    ),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+/// All the components used in the given nodes, including the nested ones.
+fn components(nodes: &[Node]) -> Vec<&Element> {
+    let mut out = vec![];
+
+    for node in nodes {
+        if let Node::Element(e) = node {
+            if e.component_path().is_some() {
+                out.push(e);
+            }
+
+            out.append(&mut components(&e.children));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use reign_view_ast::parse::parse;
+    use super::super::tokenize::tokenize;
+    use super::*;
+    use quote::quote;
+
+    fn manifest(views: &[(&str, &ItemTemplate)]) -> Manifest {
+        let mut manifest = Manifest::new(quote! { crate::views });
+
+        for (key, template) in views {
+            let (_, fields) = tokenize(
+                template,
+                &Manifest::new(manifest.path.clone()),
+                None,
+                &quote! { ::reign::view::HtmlEscaper },
+                &quote! { ::reign::view::StdFilters },
+            );
+            manifest.insert(key.to_string(), fields);
+        }
+
+        manifest
+    }
+
+    fn template(data: &str, name: &str) -> ItemTemplate {
+        parse(data.to_string(), name.to_string(), "").unwrap()
+    }
+
+    #[test]
+    fn test_order() {
+        let page = template("<template><shared:card /></template>", "Page");
+        let card = template("<template><shared:icon /></template>", "Card");
+        let icon = template("<template><i></i></template>", "Icon");
+
+        let graph = Graph::new(&[
+            ("Page", &page),
+            ("shared::Card", &card),
+            ("shared::Icon", &icon),
+        ]);
+
+        assert_eq!(graph.order(), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_order_cycle() {
+        let tree = template("<template><tree /></template>", "Tree");
+
+        assert_eq!(Graph::new(&[("Tree", &tree)]).order(), vec![0]);
+    }
+
+    #[test]
+    fn test_check() {
+        let page = template(
+            "<template><icon titel=\"a\" /><unknown /></template>",
+            "Page",
+        );
+        let icon = template("<template>{{ title }}{{ size: u32 = 1 }}</template>", "Icon");
+        let manifest = manifest(&[("Page", &page), ("Icon", &icon)]);
+
+        assert_eq!(
+            check(&page, &manifest),
+            vec![
+                (10, "component `<icon>` has no prop `titel`".to_string()),
+                (
+                    10,
+                    "component `<icon>` is missing the required prop `title`".to_string()
+                ),
+                (28, "unknown component `<unknown>`".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_spread_and_attrs() {
+        let page = template(
+            "<template><icon :..=\"other\" /><wrapper id=\"a\" /></template>",
+            "Page",
+        );
+        let icon = template("<template>{{ title }}</template>", "Icon");
+        let wrapper = template("<template><b !inherit-attrs></b></template>", "Wrapper");
+        let manifest = manifest(&[("Page", &page), ("Icon", &icon), ("Wrapper", &wrapper)]);
+
+        assert_eq!(check(&page, &manifest), vec![]);
+    }
+}
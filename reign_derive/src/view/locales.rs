@@ -0,0 +1,315 @@
+use std::{
+    collections::BTreeMap,
+    fs::read_to_string,
+    path::Path,
+};
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::LitStr;
+
+use super::display_path;
+
+/// Piece of a message, either text or the name of an argument.
+#[derive(Debug, PartialEq)]
+enum Part {
+    Text(String),
+    Arg(String),
+}
+
+/// Messages of the locales read from the `locales` folder.
+pub struct Catalogs {
+    /// Locales along with their catalog file and messages
+    locales: Vec<(String, String, BTreeMap<String, Vec<Part>>)>,
+    default: usize,
+}
+
+impl Catalogs {
+    /// Reads the `<locale>.toml` catalogs of the folder.
+    pub fn load(dir: &Path, default: &str) -> Result<Self, String> {
+        let entries = dir
+            .read_dir()
+            .map_err(|err| format!("unable to read the locales in `{}`: {}", display_path(dir), err))?;
+        let mut locales = vec![];
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.extension().map_or(true, |ext| ext != "toml") {
+                continue;
+            }
+
+            let locale = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            let file = path.to_string_lossy().to_string();
+            let source = read_to_string(&path)
+                .map_err(|err| format!("unable to read `{}`: {}", display_path(&path), err))?;
+            let messages = parse_catalog(&source.replace("\r\n", "\n"))
+                .map_err(|(line, message)| format!("{}:{}: {}", display_path(&path), line, message))?;
+
+            locales.push((locale, file, messages));
+        }
+
+        locales.sort_by(|a, b| a.0.cmp(&b.0));
+
+        match locales.iter().position(|(locale, _, _)| locale == default) {
+            Some(default) => Ok(Catalogs { locales, default }),
+            None => Err(format!(
+                "no catalog for the default locale `{}` in `{}`",
+                default,
+                display_path(dir)
+            )),
+        }
+    }
+
+    /// Checks that every message exists in every locale and
+    /// is given all of the arguments it uses and nothing else.
+    pub fn check(&self, messages: &[(Option<usize>, String, Vec<String>)]) -> Vec<(Option<usize>, String)> {
+        let mut errors = vec![];
+
+        for (pos, key, args) in messages {
+            let mut used = vec![];
+
+            for (locale, _, catalog) in &self.locales {
+                let parts = match catalog.get(key) {
+                    Some(parts) => parts,
+                    None => {
+                        errors.push((*pos, format!("message `{}` is missing in the `{}` locale", key, locale)));
+                        continue;
+                    }
+                };
+
+                for part in parts {
+                    if let Part::Arg(name) = part {
+                        if !args.contains(name) {
+                            errors.push((
+                                *pos,
+                                format!("message `{}` needs the argument `{}` in the `{}` locale", key, name, locale),
+                            ));
+                        }
+
+                        used.push(name);
+                    }
+                }
+            }
+
+            for arg in args {
+                if !used.contains(&arg) {
+                    errors.push((*pos, format!("message `{}` has no argument `{}`", key, arg)));
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// The `CATALOG` static read by the views to write their messages.
+    pub fn tokens(&self) -> TokenStream {
+        let default = self.default;
+        let mut files = vec![];
+        let mut locales = vec![];
+
+        for (locale, file, catalog) in &self.locales {
+            let messages = catalog.iter().map(|(key, parts)| {
+                let parts = parts.iter().map(|part| match part {
+                    Part::Text(text) => quote! { ::reign::view::i18n::Part::Text(#text) },
+                    Part::Arg(name) => quote! { ::reign::view::i18n::Part::Arg(#name) },
+                });
+
+                quote! { (#key, &[#(#parts),*]) }
+            });
+
+            files.push(LitStr::new(file, Span::call_site()));
+            locales.push(quote! { (#locale, &[#(#messages),*]) });
+        }
+
+        // Including the catalogs makes rustc run `views!` again when they change
+        quote! {
+            #(const _: &str = include_str!(#files);)*
+
+            pub static CATALOG: ::reign::view::i18n::Catalog =
+                ::reign::view::i18n::Catalog::new(&[#(#locales),*], #default);
+        }
+    }
+}
+
+/// Parses a catalog made of `key = "message"` lines, which can be grouped
+/// under `[section]` headers whose name is prepended to the keys, and `#`
+/// comments. Arguments are written as `{name}` in the messages, and `{{`
+/// and `}}` are written as `{` and `}`.
+///
+/// Errors are returned along with the line they are on.
+fn parse_catalog(source: &str) -> Result<BTreeMap<String, Vec<Part>>, (usize, String)> {
+    let mut messages = BTreeMap::new();
+    let mut section = String::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let line = line.trim();
+        let error = |message: &str| (i + 1, message.to_string());
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').ok_or_else(|| error("expected `]`"))?.trim();
+
+            if !is_key(name) {
+                return Err(error("expected a section name"));
+            }
+
+            section = format!("{}.", name);
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(|| error("expected `key = \"message\"`"))?;
+        let key = key.trim();
+
+        if !is_key(key) {
+            return Err(error("expected a key made of letters, digits, `_`, `-` and `.`"));
+        }
+
+        let value = string(value.trim()).map_err(&error)?;
+        let parts = parts(&value).map_err(&error)?;
+
+        if messages.insert(format!("{}{}", section, key), parts).is_some() {
+            return Err(error(&format!("message `{}{}` is defined twice", section, key)));
+        }
+    }
+
+    Ok(messages)
+}
+
+fn is_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('.').all(|piece| {
+            !piece.is_empty() && piece.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+}
+
+/// Value of a double quoted string, with `\"`, `\\`, `\n` and `\t` escapes.
+fn string(value: &str) -> Result<String, &'static str> {
+    let mut chars = value.strip_prefix('"').ok_or("expected a double quoted message")?.chars();
+    let mut out = String::new();
+
+    loop {
+        match chars.next() {
+            Some('"') => break,
+            Some('\\') => out.push(match chars.next() {
+                Some('"') => '"',
+                Some('\\') => '\\',
+                Some('n') => '\n',
+                Some('t') => '\t',
+                _ => return Err("expected `\\\"`, `\\\\`, `\\n` or `\\t`"),
+            }),
+            Some(c) => out.push(c),
+            None => return Err("expected `\"` at the end of the message"),
+        }
+    }
+
+    match chars.as_str().trim() {
+        rest if rest.is_empty() || rest.starts_with('#') => Ok(out),
+        _ => Err("expected the end of the line after the message"),
+    }
+}
+
+fn parts(message: &str) -> Result<Vec<Part>, &'static str> {
+    let mut parts = vec![];
+    let mut text = String::new();
+    let mut rest = message;
+
+    while let Some(i) = rest.find(|c| c == '{' || c == '}') {
+        text.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        if let Some(after) = rest.strip_prefix("{{").or_else(|| rest.strip_prefix("}}")) {
+            text.push_str(&rest[..1]);
+            rest = after;
+            continue;
+        }
+
+        if rest.starts_with('}') {
+            return Err("expected `}}` for a `}` in the message");
+        }
+
+        let end = rest.find('}').ok_or("expected `}` after the argument")?;
+        let name = rest[1..end].trim();
+
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err("expected the name of an argument inside `{}`");
+        }
+
+        if !text.is_empty() {
+            parts.push(Part::Text(std::mem::take(&mut text)));
+        }
+
+        parts.push(Part::Arg(name.to_string()));
+        rest = &rest[end + 1..];
+    }
+
+    text.push_str(rest);
+
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn catalogs(locales: &[(&str, &str)]) -> Catalogs {
+        Catalogs {
+            locales: locales
+                .iter()
+                .map(|(locale, source)| (locale.to_string(), String::new(), parse_catalog(source).unwrap()))
+                .collect(),
+            default: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_catalog() {
+        let messages = parse_catalog(
+            "# Shop\ntitle = \"Shop \\\"{{name}}\\\"\"\n\n[checkout]\ntotal = \"Total: {amount} {currency}\" # Sum\n",
+        )
+        .unwrap();
+
+        assert_eq!(messages["title"], vec![Part::Text("Shop \"{name}\"".to_string())]);
+        assert_eq!(
+            messages["checkout.total"],
+            vec![
+                Part::Text("Total: ".to_string()),
+                Part::Arg("amount".to_string()),
+                Part::Text(" ".to_string()),
+                Part::Arg("currency".to_string()),
+            ]
+        );
+
+        assert_eq!(parse_catalog("a = b").unwrap_err(), (1, "expected a double quoted message".to_string()));
+        assert_eq!(parse_catalog("\n[a]\nb = \"{x\"").unwrap_err(), (3, "expected `}` after the argument".to_string()));
+        assert_eq!(parse_catalog("a = \"\"\na = \"\"").unwrap_err(), (2, "message `a` is defined twice".to_string()));
+    }
+
+    #[test]
+    fn test_check() {
+        let catalogs = catalogs(&[("en", "total = \"Total: {amount}\""), ("ru", "total = \"Итого\"")]);
+        let messages = vec![
+            (Some(1), "total".to_string(), vec!["amount".to_string()]),
+            (Some(2), "total".to_string(), vec![]),
+            (Some(3), "total".to_string(), vec!["amount".to_string(), "tax".to_string()]),
+            (Some(4), "missing".to_string(), vec![]),
+        ];
+
+        assert_eq!(
+            catalogs.check(&messages),
+            vec![
+                (Some(2), "message `total` needs the argument `amount` in the `en` locale".to_string()),
+                (Some(3), "message `total` has no argument `tax`".to_string()),
+                (Some(4), "message `missing` is missing in the `en` locale".to_string()),
+                (Some(4), "message `missing` is missing in the `ru` locale".to_string()),
+            ]
+        );
+    }
+}
@@ -1,5 +1,6 @@
-mod ast;
-
+mod graph;
+mod locales;
+mod tokenize;
 
 use std::{fs::read_to_string, io::Error, env, path::{Path, PathBuf}};
 
@@ -8,60 +9,358 @@ use inflector::cases::pascalcase::to_pascal_case;
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, TokenStreamExt};
 use syn::{
-    parse::{Parse, ParseStream},
+    parse::{Error as ParseError, Parse, ParseStream},
     punctuated::Punctuated,
     token::Comma,
-    Ident, LitStr,
+    Expr, ExprLit, ExprPath, Ident, Lit, LitStr, Path as SynPath,
 };
-use once_cell::sync::Lazy;
 use regex::Regex;
 
+use reign_view_ast::{
+    minify::minify,
+    parse::{parse, Error as TemplateError, SCRIPT_SEPARATOR},
+    ItemTemplate,
+};
+use self::{
+    graph::{check, Graph},
+    locales::Catalogs,
+    tokenize::{tokenize, HotReload, Manifest},
+};
+use crate::{utils::Options, INTERNAL_ERR};
 
-use self::ast::{parse::parse, tokenize::tokenize, ItemTemplate};
-use crate::INTERNAL_ERR;
-
-// TODO: derive: Options after the paths (including changing `crate::views`)
-// Can't use parse_separated_non_empty here
 pub struct Views {
     paths: Punctuated<LitStr, Comma>,
+    module: Ident,
+    root: SynPath,
+    extension: String,
+    style_const: bool,
+    case: Case,
+    hot_reload: bool,
+    escaper: TokenStream,
+    filters: TokenStream,
+    locales: Option<String>,
+    default_locale: String,
+    minify: bool,
+}
+
+/// Case of the file and folder names of the views
+#[derive(Clone, Copy, PartialEq)]
+pub enum Case {
+    Snake,
+    Kebab,
+}
+
+impl Case {
+    fn name_regex(self) -> &'static str {
+        match self {
+            Case::Snake => "[[:alpha:]]([[:word:]]*[[:alnum:]])?",
+            Case::Kebab => "[[:alpha:]]([a-zA-Z0-9\\-]*[[:alnum:]])?",
+        }
+    }
 }
 
 impl Parse for Views {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Can't use parse_terminated here because of the options after the paths
+        let mut paths = Punctuated::new();
+
+        while input.peek(LitStr) {
+            paths.push_value(input.parse()?);
+
+            if !input.peek(Comma) || !input.peek2(LitStr) {
+                break;
+            }
+
+            paths.push_punct(input.parse()?);
+        }
+
+        let mut options: Options = input.parse()?;
+
+        let module = match options.remove("module") {
+            Some(Expr::Path(ExprPath { path, .. })) if path.get_ident().is_some() => {
+                path.get_ident().cloned().expect(INTERNAL_ERR)
+            }
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected module name")),
+            None => Ident::new("views", Span::call_site()),
+        };
+
+        let root = match options.remove("root") {
+            Some(Expr::Path(ExprPath { path, .. })) => path,
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected module path")),
+            None => SynPath::from(Ident::new("crate", Span::call_site())),
+        };
+
+        let extension = match options.remove("extension") {
+            Some(Expr::Lit(ExprLit { lit: Lit::Str(s), .. })) => s.value(),
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected string")),
+            None => "html".to_string(),
+        };
+
+        let style_const = match options.remove("style_const") {
+            Some(Expr::Lit(ExprLit { lit: Lit::Bool(b), .. })) => b.value,
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected boolean")),
+            None => true,
+        };
+
+        let case = match options.remove("case") {
+            Some(Expr::Lit(ExprLit { lit: Lit::Str(s), .. })) => match s.value().as_str() {
+                "snake" => Case::Snake,
+                "kebab" => Case::Kebab,
+                _ => return Err(ParseError::new_spanned(s, "expected `snake` or `kebab`")),
+            },
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected string")),
+            None => Case::Snake,
+        };
+
+        let hot_reload = match options.remove("hot_reload") {
+            Some(Expr::Lit(ExprLit { lit: Lit::Bool(b), .. })) => b.value,
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected boolean")),
+            None => false,
+        };
+
+        let escaper = match options.remove("escaper") {
+            Some(Expr::Path(ExprPath { path, .. })) => quote! { #path },
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected escaper path")),
+            None => quote! { ::reign::view::HtmlEscaper },
+        };
+
+        let filters = match options.remove("filters") {
+            Some(Expr::Path(ExprPath { path, .. })) => quote! { #path },
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected filters path")),
+            None => quote! { ::reign::view::StdFilters },
+        };
+
+        let locales = match options.remove("locales") {
+            Some(Expr::Lit(ExprLit { lit: Lit::Str(s), .. })) => Some(s.value()),
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected string")),
+            None => None,
+        };
+
+        let default_locale = match options.remove("default_locale") {
+            Some(Expr::Lit(ExprLit { lit: Lit::Str(s), .. })) => s.value(),
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected string")),
+            None => "en".to_string(),
+        };
+
+        let minify = match options.remove("minify") {
+            Some(Expr::Lit(ExprLit { lit: Lit::Bool(b), .. })) => b.value,
+            Some(expr) => return Err(ParseError::new_spanned(expr, "expected boolean")),
+            None => false,
+        };
+
+        if let Some(ident) = options.inner.keys().next() {
+            return Err(ParseError::new_spanned(ident, "unknown option"));
+        }
+
         Ok(Views {
-            paths: input.parse_terminated(|i| i.parse::<LitStr>())?,
+            paths,
+            module,
+            root,
+            extension,
+            style_const,
+            case,
+            hot_reload,
+            escaper,
+            filters,
+            locales,
+            default_locale,
+            minify,
         })
     }
 }
 
-fn get_dir(input: Views) -> PathBuf {
-    let mut dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+impl Views {
+    fn file_regex(&self) -> Regex {
+        Regex::new(&format!(
+            "^({})\\.{}$",
+            self.case.name_regex(),
+            regex::escape(&self.extension)
+        ))
+        .expect(INTERNAL_ERR)
+    }
+
+    fn folder_regex(&self) -> Regex {
+        Regex::new(&format!("^({})$", self.case.name_regex())).expect(INTERNAL_ERR)
+    }
+}
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+}
+
+fn get_dir(input: &Views) -> PathBuf {
+    let mut dir = manifest_dir();
 
-    for i in input.paths.into_iter() {
+    for i in input.paths.iter() {
         dir.push(i.value());
     }
 
     dir
 }
 
+/// Template collected from the views folder
+struct View {
+    path: PathBuf,
+    /// Path of the module of the file inside the views, see `module_path`
+    module: String,
+    /// Name of the main template in the file
+    root: String,
+    /// Contents of the template file, kept for error messages
+    source: String,
+    template: ItemTemplate,
+}
+
+impl View {
+    // Errors without a position, like missing messages, only name the file
+    fn error(&self, pos: Option<usize>, message: String) -> String {
+        match pos {
+            Some(pos) => TemplateError::new(display_path(&self.path), self.source.clone(), pos, message).to_string(),
+            None => format!("{}\n --> {}", message, display_path(&self.path)),
+        }
+    }
+}
+
 pub (crate) fn views(input: Views) -> TokenStream {
-    let dir = get_dir(input);
+    let dir = get_dir(&input);
+    let module = &input.module;
+    let root = &input.root;
 
     let mut i = 0;
 
     let mut templates = vec![];
-    collect_views(&dir, &mut templates).unwrap();
+    let mut errors = vec![];
 
-    for (path, _) in &templates {
-        eprintln!("p: {}", path.display());
+    if let Err(err) = collect_views(&dir, &input, &mut templates, &mut errors) {
+        errors.push(format!("unable to read the views in `{}`: {}", display_path(&dir), err));
+        return compile_errors(&errors);
     }
 
-    let style = templates.iter().map(|(_, item)| item.style.as_str()).collect::<String>();
+    // Components of the templates which failed to parse would be reported
+    // as unknown, so only check them once all the templates are parsed.
+    let parsed = errors.is_empty();
+
+    let style = templates.iter().map(|view| view.template.style.as_str()).collect::<String>();
     let style_lit = LitStr::new(&style, Span::call_site());
+    let script = templates
+        .iter()
+        .map(|view| view.template.script.as_str())
+        .filter(|script| !script.is_empty())
+        .collect::<Vec<_>>()
+        .join(SCRIPT_SEPARATOR);
+    let script_lit = LitStr::new(&script, Span::call_site());
+    let style_const = if input.style_const {
+        quote! {
+            pub const STYLE: &str = #style_lit;
+            pub const SCRIPT: &str = #script_lit;
+        }
+    } else {
+        quote! {}
+    };
+
+    let catalogs = match &input.locales {
+        Some(locales) => match Catalogs::load(&manifest_dir().join(locales), &input.default_locale) {
+            Ok(catalogs) => Some(catalogs),
+            Err(err) => {
+                errors.push(err);
+                return compile_errors(&errors);
+            }
+        },
+        None => None,
+    };
+    let catalog = catalogs.as_ref().map_or_else(TokenStream::new, Catalogs::tokens);
+
+    let keys: Vec<String> = templates
+        .iter()
+        .map(|view| view_key(view.path.strip_prefix(&dir).unwrap(), &view.template.name))
+        .collect();
+
+    // Collect the fields of all the views first so that the components
+    // used in a view know which of their fields are optional.
+    let mut manifest = Manifest::new(quote! { #root::#module });
+
+    for (key, view) in keys.iter().zip(&templates) {
+        let (_, fields) = tokenize(
+            &view.template,
+            &Manifest::new(manifest.path.clone()),
+            None,
+            &input.escaper,
+            &input.filters,
+        );
+        manifest.insert(key.clone(), fields);
+    }
+
+    // Build the components before the views using them so that the
+    // types of the component fields are forwarded to the views.
+    let graph = Graph::new(
+        &keys
+            .iter()
+            .zip(&templates)
+            .map(|(key, view)| (key.as_str(), &view.template))
+            .collect::<Vec<_>>(),
+    );
+
+    let mut views = vec![TokenStream::new(); templates.len()];
+    let mut view_errors = vec![vec![]; templates.len()];
+
+    let crate_dir = manifest_dir();
+    let crate_dir_str = crate_dir.to_string_lossy();
+
+    for i in graph.order() {
+        let view = &templates[i];
+        let path = view.path.strip_prefix(&crate_dir).unwrap_or(&view.path).to_string_lossy();
+        let hot_reload = HotReload {
+            dir: &crate_dir_str,
+            path: &path,
+            module: &view.module,
+            root: &view.root,
+            minify: input.minify,
+        };
+        let (tokens, fields) = tokenize(
+            &view.template,
+            &manifest,
+            if input.hot_reload { Some(&hot_reload) } else { None },
+            &input.escaper,
+            &input.filters,
+        );
+
+        let message_errors = match &catalogs {
+            Some(catalogs) => catalogs.check(&fields.messages),
+            None => fields
+                .messages
+                .iter()
+                .take(1)
+                .map(|(pos, _, _)| (*pos, "`t!` needs the `locales` option of `views!`".to_string()))
+                .collect(),
+        };
+
+        view_errors[i] = fields
+            .errors
+            .iter()
+            .chain(&message_errors)
+            .map(|(pos, message)| templates[i].error(*pos, message.clone()))
+            .collect();
+
+        manifest.insert(keys[i].clone(), fields);
+        views[i] = tokens;
+    }
+
+    for (view, view_errors) in templates.iter().zip(view_errors) {
+        errors.extend(view_errors);
 
-    let modules = templates.into_iter().map(|(path, template)| {
-        let (file_view, _idents) = tokenize(&template);
+        if parsed {
+            errors.extend(
+                check(&view.template, &manifest)
+                    .into_iter()
+                    .map(|(pos, message)| view.error(Some(pos), message)),
+            );
+        }
+    }
+
+    if !errors.is_empty() {
+        return compile_errors(&errors);
+    }
 
+    let modules = templates.into_iter().zip(views).map(|(View { path, .. }, file_view)| {
         // Incldue source as a string so that rustc knows it needs
         // to run this again when the source code changes.
 
@@ -86,13 +385,54 @@ pub (crate) fn views(input: Views) -> TokenStream {
     let output = build_mod_tree(&modules);
 
     quote! {
-        pub mod views {
+        pub mod #module {
             #output
-            pub const STYLE: &str = #style_lit;
+            #style_const
+            #catalog
         }
     }
 }
 
+fn compile_errors(errors: &[String]) -> TokenStream {
+    quote! {
+        #(compile_error!(#errors);)*
+    }
+}
+
+/// Path shown in error messages, relative to the crate when possible
+fn display_path(path: &Path) -> String {
+    let manifest_dir = manifest_dir();
+
+    path.strip_prefix(&manifest_dir).unwrap_or(path).display().to_string()
+}
+
+/// Path of the view inside the views module, e.g. `shared::Button`
+fn view_key(relative_path: &Path, name: &str) -> String {
+    let module = module_path(relative_path);
+
+    if module.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}::{}", module, name)
+    }
+}
+
+/// Path of the module of a template file inside the views module, e.g. `shared`
+fn module_path(relative_path: &Path) -> String {
+    relative_path
+        .parent()
+        .into_iter()
+        .flat_map(|p| p.iter())
+        .map(|x| module_name(&x.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Name of the module for a views folder
+fn module_name(folder: &str) -> String {
+    folder.replace('-', "_")
+}
+
 /// Takes input, in the order of a depth first search, with a list of paths
 /// relative to the root views folder, and coverts it into a module tree.
 fn build_mod_tree(mut input: &[(PathBuf, TokenStream)]) -> TokenStream {
@@ -103,7 +443,7 @@ fn build_mod_tree_recurse(root: &Path, input: &mut &[(PathBuf, TokenStream)]) ->
     let mut out = TokenStream::new(); // TODO: Potentially pass this down for efficiency?
 
     loop {
-        if input.len() == 0 {
+        if input.is_empty() {
             // We're done
             break
         }
@@ -120,7 +460,7 @@ fn build_mod_tree_recurse(root: &Path, input: &mut &[(PathBuf, TokenStream)]) ->
             // TODO: Only recurse one path componenet at a time (this breaks if there are empty folders)
             let tokens = build_mod_tree_recurse(parent, input);
             let mod_name = parent.file_name().unwrap().to_str().expect("Non-utf8 file/dir name");
-            let mod_ident = Ident::new(mod_name, Span::call_site());
+            let mod_ident = Ident::new(&module_name(mod_name), Span::call_site());
 
             out.append_all(quote! {
                 pub mod #mod_ident {
@@ -141,60 +481,83 @@ fn build_mod_tree_recurse(root: &Path, input: &mut &[(PathBuf, TokenStream)]) ->
 
 fn collect_views(
     path: &Path,
+    input: &Views,
     // Recursive function requires mutable output collector...
-    out: &mut Vec<(PathBuf, ItemTemplate)>,
+    out: &mut Vec<View>,
+    errors: &mut Vec<String>,
 ) -> Result<(), Error> {
-    for entry in path.read_dir().expect(INTERNAL_ERR) {
-        if let Ok(entry) = entry {
-            let new_path = entry.path();
-            let file_name_os_str = entry.file_name();
-            let file_name: &str = &*file_name_os_str.to_string_lossy();
-
-            eprintln!("np: {}", new_path.display());
-            if new_path.is_dir() {
-                if !FOLDER_REGEX.is_match(&file_name) {
-                    eprintln!("\tnot folder");
-                    continue;
-                }
+    let (file_regex, folder_regex) = (input.file_regex(), input.folder_regex());
 
-                eprintln!("\tfolder");
-                collect_views(
-                    &new_path,
-                    out,
-                )?;
+    for entry in path.read_dir()?.flatten() {
+        let new_path = entry.path();
+        let file_name_os_str = entry.file_name();
+        let file_name: &str = &file_name_os_str.to_string_lossy();
 
+        if new_path.is_dir() {
+            if !folder_regex.is_match(file_name) {
                 continue;
             }
 
-            if !FILE_REGEX.is_match(&file_name) {
-                eprintln!("\tnot file");
+            collect_views(
+                &new_path,
+                input,
+                out,
+                errors,
+            )?;
+
+            continue;
+        }
+
+        if !file_regex.is_match(file_name) {
+            continue;
+        }
+
+        let file_base_name = file_name.trim_end_matches(&format!(".{}", input.extension));
+        let template_name = to_pascal_case(file_base_name);
+        let module = module_path(new_path.strip_prefix(get_dir(input)).unwrap_or(&new_path));
+        let data = read_to_string(&new_path)?
+            .replace("\r\n", "\n");
+
+        let mut template_item = match parse(data.clone(), template_name, &module) {
+            Ok(template_item) => template_item,
+            Err(mut err) => {
+                // Keep going so that the errors of all the templates are reported
+                err.filename = Some(display_path(&new_path));
+                errors.push(err.to_string());
                 continue;
             }
+        };
 
-            eprintln!("\tfile");
-
-            let file_base_name = file_name.trim_end_matches(".html");
-            let template_name = to_pascal_case(file_base_name);
-            let data = read_to_string(new_path)?
-                .replace("\r\n", "\n");
-            // TODO: Error reporting improvements
-            let template_item = parse(data, template_name).expect("Failed to parse template");
+        if input.minify {
+            minify(&mut template_item);
+        }
 
-            let mut view_path = path.to_path_buf();
-            view_path.push(file_name);
-            out.push((view_path, template_item));
+        let named = std::mem::take(&mut template_item.named);
+        let root = template_item.name.clone();
+
+        out.push(View {
+            path: new_path.clone(),
+            module: module.clone(),
+            root: root.clone(),
+            source: data.clone(),
+            template: template_item,
+        });
+
+        // Named templates are views in the same module as the file
+        for item in named {
+            out.push(View {
+                path: new_path.clone(),
+                module: module.clone(),
+                root: root.clone(),
+                source: data.clone(),
+                template: item,
+            });
         }
     }
 
     Ok(())
 }
 
-static FILE_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^([[:alpha:]]([[:word:]]*[[:alnum:]])?)\.html$").expect(INTERNAL_ERR)
-});
-static FOLDER_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^([[:alpha:]]([[:word:]]*[[:alnum:]])?)").expect(INTERNAL_ERR));
-
 // fn recurse<O, I, P>(
 //     path: &Path,
 //     relative_path: &str,
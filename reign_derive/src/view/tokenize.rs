@@ -0,0 +1,1512 @@
+use inflector::cases::snakecase::to_snake_case;
+use proc_macro2::{Delimiter, Group, Punct, Spacing, Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::{punctuated::{Pair, Punctuated}, parse2, Ident, LitStr};
+
+use reign_view_ast::{
+    expr::{Expr, KEYWORDS},
+    parse::consts::{URL_ATTRIBUTES, VOID_TAGS},
+    *,
+};
+
+pub use manifest::{key, Manifest};
+pub use view_fields::ViewFields;
+
+mod expr;
+mod manifest;
+mod pat;
+mod view_fields;
+
+/// Template file read again on every render in debug builds, see `reign_view::hot`.
+pub struct HotReload<'a> {
+    /// Absolute path of the crate the views are compiled in
+    pub dir: &'a str,
+    /// Path of the template file relative to `dir`
+    pub path: &'a str,
+    /// Path of the module of the file inside the views, like `shared`
+    pub module: &'a str,
+    /// Name of the main template in the file
+    pub root: &'a str,
+    /// Whether the template is minified, see `ast::minify`
+    pub minify: bool,
+}
+
+/// Generates the view for the template, whose values are escaped with the
+/// `escaper` expression, a value implementing `reign::view::Escaper`, and
+/// filtered with the `filters` expression, a value implementing `reign::view::Filters`.
+pub fn tokenize(
+    template: &ItemTemplate,
+    manifest: &Manifest,
+    hot_reload: Option<&HotReload>,
+    escaper: &TokenStream,
+    filters: &TokenStream,
+) -> (TokenStream, ViewFields) {
+    let template_name = Ident::new(&template.name, Span::call_site());
+    let docs = template.docs.trim();
+    let docs = if docs.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[doc = #docs] }
+    };
+
+    let mut fmt_tokens = TokenStream::new();
+    let mut idents = ViewFields::new();
+
+    idents.scope = template.scope.clone();
+
+    {
+        let scopes = ViewFields::new();
+
+        // Generate attrs so they can be used for type ascription,
+        // but don't actually emit the code since we just throw it away.
+        let _attrs = attrs_tokens(&template.attrs, &mut idents, &scopes);
+
+        // Template.tokenize(tokens, idents, scopes)
+        let children = nodes_tokens(&template.children, &mut idents, &scopes, manifest);
+
+        // TODO: We aren't considering top level if/for directives, forbid them.
+        fmt_tokens.append_all(merge_static(
+            quote! {
+                #(#children)*
+            }
+        ))
+    }
+
+    let catalog = if idents.messages.is_empty() {
+        quote! {}
+    } else {
+        let views = &manifest.path;
+
+        quote! { let __reign_catalog = &#views::CATALOG; }
+    };
+
+    let attrs_ident = Ident::new("attrs", Span::call_site());
+    let (attrs_field, attrs_default) = if idents.fallthrough {
+        if idents.contains(&attrs_ident) {
+            idents.error("`attrs` is the field of the attributes forwarded with `!inherit-attrs`".to_string());
+        }
+
+        (
+            quote! { pub attrs: ::reign::view::Attrs<'a>, },
+            quote! { attrs: Default::default(), },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    let size_hint = static_len(&template.children, template.scope.as_deref());
+    let (template_args, types) = (idents.keys(), idents.values());
+    let template_arg_idents: Vec<Ident> = template_args.iter().map(|x| x.0.clone()).collect();
+    let hot_reload = hot_reload.map_or_else(TokenStream::new, |hot| {
+        hot_reload_tokens(hot, &template.name, &template_arg_idents)
+    });
+
+    let (mut required, mut required_types, mut optional, mut defaults) = (vec![], vec![], vec![], vec![]);
+
+    for (ident, ty) in template_arg_idents.iter().zip(types.iter()) {
+        if idents.is_optional(ident) {
+            optional.push(ident);
+            defaults.push(idents.default_value(ident));
+        } else {
+            required.push(ident);
+            required_types.push(ty);
+        }
+    }
+
+    (
+        quote! {
+            #docs
+            pub struct #template_name<'a> {
+                #(pub #template_arg_idents: #types,)*
+                #attrs_field
+                pub slots: ::reign::view::Slots<'a>,
+                pub marker: std::marker::PhantomData<& 'a ()>,
+            }
+
+            impl<'a> #template_name<'a> {
+                /// Builds the view from its required fields, filling the optional
+                /// fields with their default values.
+                #[allow(clippy::too_many_arguments, clippy::new_without_default)]
+                pub fn new(#(#required: #required_types),*) -> Self {
+                    #template_name {
+                        #(#required,)*
+                        #(#optional: #defaults,)*
+                        #attrs_default
+                        slots: Default::default(),
+                        marker: std::marker::PhantomData,
+                    }
+                }
+            }
+
+            #[allow(unused_variables)]
+            impl<'a> ::reign::view::Render for #template_name<'a> {
+                fn render_in(&self, f: &mut dyn std::fmt::Write, __reign_locale: Option<&str>) -> std::fmt::Result {
+                    #[allow(unused_imports)]
+                    use ::reign::view::{
+                        attr::{ViaAttrDisplay, ViaAttrEscape, ViaAttrValue},
+                        binding::{ViaClassDisplay, ViaClassValue, ViaStyleDisplay, ViaStyleValue},
+                        escape::{ViaDisplay, ViaEscape, Wrap},
+                        filters::Filters,
+                    };
+
+                    let __reign_escaper: &dyn ::reign::view::Escaper = &#escaper;
+                    let __reign_filters = &#filters;
+                    #catalog
+
+                    #hot_reload
+                    #fmt_tokens
+                    Ok(())
+                }
+
+                fn size_hint(&self) -> usize {
+                    #size_hint
+                }
+            }
+
+            // Views are markup already, so they are written without escaping
+            impl<'a> ::reign::view::Escape for #template_name<'a> {
+                fn escape(
+                    &self,
+                    f: &mut dyn std::fmt::Write,
+                    context: ::reign::view::escape::Context,
+                    escaper: &dyn ::reign::view::Escaper,
+                ) -> std::fmt::Result {
+                    match context {
+                        ::reign::view::escape::Context::Text => ::reign::view::Render::render(self, f),
+                        _ => escaper.escape(f, context, self),
+                    }
+                }
+            }
+
+            impl<'a> std::fmt::Display for #template_name<'a> {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    ::reign::view::Render::render(self, f)
+                }
+            }
+        },
+        idents,
+    )
+}
+
+fn hot_reload_tokens(hot: &HotReload, name: &str, fields: &[Ident]) -> TokenStream {
+    let (dir, path, module, root, minify) = (hot.dir, hot.path, hot.module, hot.root, hot.minify);
+    let names: Vec<String> = fields.iter().map(|x| x.to_string()).collect();
+
+    quote! {
+        #[cfg(debug_assertions)]
+        {
+            #[allow(unused_imports)]
+            use ::reign::view::hot::{ViaOpaque, ViaValue, Wrap};
+
+            let fields: &[(&str, ::reign::view::hot::Value)] = &[
+                #((#names, (&&Wrap(&self.#fields)).value()),)*
+            ];
+
+            let template = ::reign::view::hot::Template {
+                dir: #dir,
+                path: #path,
+                module: #module,
+                root: #root,
+                name: #name,
+                minify: #minify,
+            };
+
+            if let Some(result) = ::reign::view::hot::render(f, &template, fields, &self.slots, __reign_escaper) {
+                return result;
+            }
+        }
+    }
+}
+
+/// Length of the static content of the nodes, used as the size hint of the view.
+fn static_len(nodes: &[Node], scope: Option<&str>) -> usize {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(t) => parts_len(&t.content),
+            Node::Element(e) => {
+                let mut len = static_len(&e.children, scope);
+
+                if e.name.split(':').count() == 1 && is_reserved_tag(&e.name) {
+                    // `<name>` and `</name>`
+                    len += e.name.len() * 2 + 5;
+
+                    // ` scope`
+                    len += scope.map_or(0, |scope| scope.len() + 1);
+
+                    for attr in &e.attrs {
+                        if let Attribute::Normal(n) = attr {
+                            // ` name="value"`
+                            len += n.name.len() + parts_len(&n.value.parts) + 4;
+                        }
+                    }
+                }
+
+                len
+            }
+            _ => 0,
+        })
+        .sum()
+}
+
+fn parts_len(parts: &[StringPart]) -> usize {
+    parts
+        .iter()
+        .map(|part| match part {
+            StringPart::Normal(s) => s.len(),
+            StringPart::Expr(_) => 0,
+        })
+        .sum()
+}
+
+trait Tokenize {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields);
+}
+
+/// Variables bound by the pattern of a `!for`, `!case` or `let`.
+fn declared(pat: &impl Tokenize) -> ViewFields {
+    let mut declared = ViewFields::new();
+    let mut tokens = TokenStream::new();
+    let scopes = ViewFields::new();
+
+    pat.tokenize(&mut tokens, &mut declared, &scopes);
+    declared
+}
+
+trait NodeTokens {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields, manifest: &Manifest);
+}
+
+trait ElementTokens {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields, manifest: &Manifest);
+
+    fn tokenize_with_empty(
+        &self,
+        tokens: &mut TokenStream,
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+        manifest: &Manifest,
+        empty: Option<&Element>,
+    );
+
+    fn raw_children_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields, context: Context) -> Vec<TokenStream>;
+
+    fn html_attrs_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields) -> Vec<TokenStream>;
+
+    fn binding_tokens(&self, name: &str, idents: &mut ViewFields, scopes: &ViewFields) -> TokenStream;
+
+    fn spread_tokens(spread: &VariableAttribute, idents: &mut ViewFields, scopes: &ViewFields) -> TokenStream;
+
+    fn inherited_tokens(idents: &mut ViewFields) -> TokenStream;
+
+    fn children_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields, manifest: &Manifest) -> Vec<TokenStream>;
+
+    fn component_slots(&self, idents: &mut ViewFields, scopes: &ViewFields, manifest: &Manifest) -> Vec<TokenStream>;
+
+    fn component_attrs(
+        &self,
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+        path: &[Ident],
+        manifest: &Manifest,
+    ) -> Vec<TokenStream>;
+
+    fn end_tokens(&self) -> TokenStream;
+}
+
+trait ValueTokens {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields, context: Context);
+
+    fn prop_tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields);
+}
+
+/// Part of the page a value is written into, mirrors `reign::view::Context`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Context {
+    Text,
+    Attribute,
+    Url,
+    UrlPart,
+    Script,
+    Style,
+}
+
+/// Same as `reign::view::escape::attribute_context` for the attributes
+/// whose name is known when the view is generated.
+fn attribute_context(name: &str) -> Context {
+    let name = name.to_ascii_lowercase();
+
+    if name.starts_with("on") {
+        Context::Script
+    } else if name == "style" {
+        Context::Style
+    } else if URL_ATTRIBUTES.contains(&name.as_str()) {
+        Context::Url
+    } else {
+        Context::Attribute
+    }
+}
+
+impl NodeTokens for Node {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields, manifest: &Manifest) {
+        match self {
+            Node::Element(e) => e.tokenize(tokens, idents, scopes, manifest),
+            Node::Comment(c) => c.tokenize(tokens, idents, scopes),
+            Node::Text(t) => t.tokenize(tokens, idents, scopes),
+            Node::Doctype(d) => d.tokenize(tokens, idents, scopes),
+        };
+    }
+}
+
+impl ElementTokens for Element {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields, manifest: &Manifest) {
+        self.tokenize_with_empty(tokens, idents, scopes, manifest, None)
+    }
+
+    // `empty` is the `!empty` sibling of an element with `!for`
+    #[allow(clippy::cognitive_complexity)]
+    fn tokenize_with_empty(
+        &self,
+        tokens: &mut TokenStream,
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+        manifest: &Manifest,
+        empty: Option<&Element>,
+    ) {
+        let errors = idents.errors.len();
+        let tag_pieces: Vec<&str> = self.name.split(':').collect();
+        let mut new_scopes = scopes.clone();
+
+        // Check for loop to see what variables are defined for this loop (`scopes`)
+        if let Some(attr_for) = self.control_attr("for") {
+            if let Code::For(for_) = &attr_for.value {
+                new_scopes.append(declared(&for_.pat));
+                new_scopes.push(Ident::new("loop", Span::call_site()));
+            }
+        }
+
+        // Same for the bindings of a match arm
+        if let Some(attr_case) = self.control_attr("case") {
+            if let Code::Case(case) = &attr_case.value {
+                new_scopes.append(declared(&case.pats));
+            }
+        }
+
+        // And for the bindings of `!if let` and `!else-if let`
+        if let Some(attr_if) = self.control_attr("if").or_else(|| self.control_attr("else-if")) {
+            if let Code::Let(let_) = &attr_if.value {
+                new_scopes.append(declared(&let_.pat));
+            }
+        }
+
+        if self.inherits_attrs() && (self.name == "template" || tag_pieces.len() > 1 || !is_reserved_tag(&self.name)) {
+            idents.error("expected an html element with `!inherit-attrs`".to_string());
+        }
+
+        let mut elem = if self.name == "template" {
+            let children = self.children_tokens(idents, &new_scopes, manifest);
+
+            quote! {
+                #(#children)*
+            }
+        } else if tag_pieces.len() == 1 && is_reserved_tag(&self.name) {
+            let scope = idents.scope.as_ref().map_or_else(String::new, |scope| format!(" {}", scope));
+            let start_tag = LitStr::new(&format!("<{}{}", &self.name, scope), Span::call_site());
+            let attrs = self.html_attrs_tokens(idents, &new_scopes);
+            let children = match self.name.as_str() {
+                "script" => self.raw_children_tokens(idents, &new_scopes, Context::Script),
+                "style" => self.raw_children_tokens(idents, &new_scopes, Context::Style),
+                _ => self.children_tokens(idents, &new_scopes, manifest),
+            };
+            let end_tokens = self.end_tokens();
+
+            quote! {
+                f.write_str(#start_tag)?;
+                #(#attrs)*
+                f.write_str(">")?;
+                #(#children)*
+                #end_tokens
+            }
+        } else if self.name == "slot" {
+            let name = LitStr::new(&self.slot_name(), Span::call_site());
+            let fallback = self.children_tokens(idents, &new_scopes, manifest);
+
+            quote! {
+                if let Some(slot) = self.slots.get(#name) {
+                    slot(f)?;
+                } else {
+                    #(#fallback)*
+                }
+            }
+        } else {
+            if self.control_attr("match").is_some() {
+                idents.error("expected an html element or `template` with `!match`".to_string());
+            }
+
+            let path = convert_tag_name(tag_pieces);
+            let attrs = self.component_attrs(idents, &new_scopes, &path, manifest);
+            let slots = self.component_slots(idents, &new_scopes, manifest);
+
+            let views = &manifest.path;
+
+            let render = quote! {
+                ::reign::view::Render::render_in(&#views::#(#path)::* {
+                    #(#attrs,)*
+                    slots: ::reign::view::Slots::new(&[#(#slots),*]),
+                    marker: std::marker::PhantomData,
+                }, f, __reign_locale)?;
+            };
+
+            // The fields which are not given are taken from `:..="props"`
+            if let Some(spread) = self.variable_attr("..") {
+                let mut props = TokenStream::new();
+
+                spread.value.tokenize(&mut props, idents, &new_scopes);
+
+                quote! {
+                    {
+                        let __reign_props = &(#props);
+                        #render
+                    }
+                }
+            } else {
+                render
+            }
+        };
+
+        elem = if let Some(r_case) = self.control_attr("case") {
+            // Match arm
+            let mut case = TokenStream::new();
+            r_case.value.tokenize(&mut case, idents, scopes);
+
+            quote! {
+                #case => {
+                    #elem
+                }
+            }
+        } else if let Some(Code::For(r_for)) = self.control_attr("for").map(|attr| &attr.value) {
+            // For loop, keeping track of the iterations for `loop` and `!empty`
+            let (mut pat, mut expr) = (TokenStream::new(), TokenStream::new());
+            let empty = empty.map(|e| {
+                let mut ts = TokenStream::new();
+
+                e.tokenize(&mut ts, idents, scopes, manifest);
+                ts
+            });
+
+            r_for.pat.tokenize(&mut pat, &mut ViewFields::new(), scopes);
+            r_for.expr.tokenize(&mut expr, idents, scopes);
+
+            quote! {
+                {
+                    let mut __reign_iter = ::std::iter::IntoIterator::into_iter(#expr).peekable();
+                    let mut __reign_index = 0;
+
+                    while let Some(#pat) = __reign_iter.next() {
+                        let __reign_loop = ::reign::view::Loop {
+                            index: __reign_index,
+                            first: __reign_index == 0,
+                            last: __reign_iter.peek().is_none(),
+                        };
+
+                        #elem
+                        __reign_index += 1;
+                    }
+
+                    if __reign_index == 0 {
+                        #empty
+                    }
+                }
+            }
+        } else if let Some(r_if) = self.control_attr("if") {
+            // If condition
+            let mut if_expr = TokenStream::new();
+            r_if.value.tokenize(&mut if_expr, idents, scopes);
+
+            quote! {
+                if #if_expr {
+                    #elem
+                }
+            }
+        } else if let Some(r_else_if) = self.control_attr("else-if") {
+            // Else If condition
+            let mut if_expr = TokenStream::new();
+            r_else_if.value.tokenize(&mut if_expr, idents, scopes);
+
+            quote! {
+                else if #if_expr {
+                    #elem
+                }
+            }
+        } else if self.control_attr("else").is_some() {
+            // Else condition
+            quote! {
+                else {
+                    #elem
+                }
+            }
+        } else {
+            elem
+        };
+
+        idents.locate(errors, self.pos);
+        tokens.append_all(elem);
+    }
+
+    // Text inside of `<script>` and `<style>` elements is escaped for
+    // the language of the element instead of html
+    fn raw_children_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields, context: Context) -> Vec<TokenStream> {
+        self.children
+            .iter()
+            .map(|child| match child {
+                Node::Text(t) => {
+                    let errors = idents.errors.len();
+                    let tokens = parts_tokens(&t.content, idents, scopes, false, context);
+
+                    idents.locate(errors, t.pos);
+                    tokens
+                }
+                _ => {
+                    idents.error(format!("expected only text inside of `<{}>`", self.name));
+                    TokenStream::new()
+                }
+            })
+            .collect()
+    }
+
+    // The `class` and `style` attributes are merged with `:class` and `:style`
+    // into a single attribute written where the first of them is
+    //
+    // With `:..` or `!inherit-attrs`, the spread or forwarded attributes
+    // are written where it is, and their `class` and `style` are merged with
+    // those of the element.
+    fn html_attrs_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields) -> Vec<TokenStream> {
+        let spread = self.has_spread() || self.inherits_attrs();
+        let mut merged = vec![];
+        let mut tokens = vec![];
+
+        for attr in &self.attrs {
+            let name = match attr {
+                Attribute::Normal(n) => n.name.as_str(),
+                Attribute::Variable(v) => v.name.as_str(),
+                Attribute::Control(c) if c.name == "inherit-attrs" => "..",
+                _ => "",
+            };
+
+            if name == ".." {
+                let attrs = match attr {
+                    Attribute::Variable(v) => Self::spread_tokens(v, idents, scopes),
+                    _ => Self::inherited_tokens(idents),
+                };
+
+                tokens.push(quote! {
+                    ::reign::view::Attrs::write(&(#attrs), f, __reign_escaper, &["class", "style"])?;
+                });
+
+                for name in ["class", "style"] {
+                    if !self.has_attr(name) && !merged.contains(&name) {
+                        merged.push(name);
+                        tokens.push(self.binding_tokens(name, idents, scopes));
+                    }
+                }
+
+                continue;
+            }
+
+            if (name == "class" || name == "style") && (self.variable_attr(name).is_some() || spread) {
+                if !merged.contains(&name) {
+                    merged.push(name);
+                    tokens.push(self.binding_tokens(name, idents, scopes));
+                }
+
+                continue;
+            }
+
+            let mut ts = TokenStream::new();
+
+            attr.tokenize(&mut ts, idents, scopes);
+            tokens.push(ts);
+        }
+
+        tokens
+    }
+
+    fn binding_tokens(&self, name: &str, idents: &mut ViewFields, scopes: &ViewFields) -> TokenStream {
+        let (list, add) = if name == "class" {
+            (quote! { ClassList }, quote! { add_class })
+        } else {
+            (quote! { StyleList }, quote! { add_style })
+        };
+        let mut adds = vec![];
+
+        for attr in &self.attrs {
+            match attr {
+                Attribute::Normal(n) if n.name == name && name == "style" => {
+                    // Built apart so that an interpolated value can't add declarations
+                    adds.push(if n.value.has_expr() {
+                        let parts = parts_tokens(&n.value.parts, idents, scopes, false, Context::Style);
+
+                        quote! {
+                            {
+                                use ::std::fmt::Write as _;
+
+                                let mut __reign_style = ::std::string::String::new();
+
+                                {
+                                    let f = &mut __reign_style;
+                                    #parts
+                                }
+
+                                __reign_list.add_static(::std::borrow::Cow::Owned(__reign_style));
+                            }
+                        }
+                    } else {
+                        let mut value = TokenStream::new();
+
+                        n.value.prop_tokenize(&mut value, idents, scopes);
+                        quote! { __reign_list.add_static(::std::borrow::Cow::Borrowed(#value)); }
+                    });
+                }
+                Attribute::Normal(n) if n.name == name => {
+                    let mut value = TokenStream::new();
+
+                    n.value.prop_tokenize(&mut value, idents, scopes);
+                    adds.push(if n.value.has_expr() {
+                        quote! { __reign_list.add(::std::borrow::Cow::Owned(#value)); }
+                    } else {
+                        quote! { __reign_list.add(::std::borrow::Cow::Borrowed(#value)); }
+                    });
+                }
+                Attribute::Variable(v) if v.name == name => {
+                    let mut value = TokenStream::new();
+
+                    v.value.tokenize(&mut value, idents, scopes);
+
+                    // Bound first so that temporaries live as long as the list
+                    adds.push(quote! {
+                        let __reign_value = &(#value);
+                        (&&Wrap(__reign_value)).#add(&mut __reign_list);
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(spread) = self.variable_attr("..") {
+            let attrs = Self::spread_tokens(spread, idents, scopes);
+
+            adds.push(quote! {
+                __reign_list.add_attr(::reign::view::Attrs::get(&(#attrs), #name));
+            });
+        }
+
+        if self.inherits_attrs() {
+            let attrs = Self::inherited_tokens(idents);
+
+            adds.push(quote! {
+                __reign_list.add_attr(::reign::view::Attrs::get(&(#attrs), #name));
+            });
+        }
+
+        quote! {
+            {
+                let mut __reign_list = ::reign::view::binding::#list::new();
+                #(#adds)*
+                __reign_list.write(f, __reign_escaper)?;
+            }
+        }
+    }
+
+    fn spread_tokens(spread: &VariableAttribute, idents: &mut ViewFields, scopes: &ViewFields) -> TokenStream {
+        let mut tokens = TokenStream::new();
+
+        spread.value.tokenize(&mut tokens, idents, scopes);
+        tokens
+    }
+
+    // The `attrs` field holds the attributes forwarded to this view
+    fn inherited_tokens(idents: &mut ViewFields) -> TokenStream {
+        idents.fallthrough = true;
+        quote! { self.attrs }
+    }
+
+    // With `!match`, the children are the arms of the match
+    fn children_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields, manifest: &Manifest) -> Vec<TokenStream> {
+        let r_match = match self.control_attr("match") {
+            Some(r_match) => r_match,
+            None => return nodes_tokens(&self.children, idents, scopes, manifest),
+        };
+
+        let mut expr = TokenStream::new();
+        let mut arms = vec![];
+
+        r_match.value.tokenize(&mut expr, idents, scopes);
+
+        for child in &self.children {
+            match child {
+                Node::Element(e) if e.control_attr("case").is_some() => {
+                    let mut ts = TokenStream::new();
+
+                    e.tokenize(&mut ts, idents, scopes, manifest);
+                    arms.push(ts);
+                }
+                Node::Comment(_) => {}
+                _ if child.is_blank() => {}
+                _ => idents.error("expected only `!case` elements inside `!match`".to_string()),
+            }
+        }
+
+        // Match ergonomics bind the fields by reference when the value is one
+        vec![quote! {
+            match #expr {
+                #(#arms)*
+            }
+        }]
+    }
+
+    // Children inside `<template #name>` fill the named slots while the
+    // remaining children, if there are any, fill the default slot.
+    fn component_slots(&self, idents: &mut ViewFields, scopes: &ViewFields, manifest: &Manifest) -> Vec<TokenStream> {
+        let mut slots = vec![];
+        let mut has_default = false;
+
+        for child in &self.children {
+            if let Node::Element(e) = child {
+                if let Some(name) = e.template_name() {
+                    let children = nodes_tokens(&e.children, idents, scopes, manifest);
+                    slots.push(slot_tokens(name.trim_start_matches('#'), children));
+                    continue;
+                }
+            }
+
+            has_default = has_default || !child.is_blank();
+        }
+
+        if has_default {
+            // `nodes_tokens` skips over the named templates
+            let children = nodes_tokens(&self.children, idents, scopes, manifest);
+            slots.insert(0, slot_tokens("default", children));
+        }
+
+        slots
+    }
+
+    // Fields which are not given are taken from `:..="props"` or filled with
+    // their default values, and the types of the fields of the component are
+    // forwarded to the fields of this view which are passed directly to it.
+    //
+    // When the component forwards its attributes with `!inherit-attrs`, the
+    // attributes which are not its fields are given in its `attrs` field.
+    fn component_attrs(
+        &self,
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+        path: &[Ident],
+        manifest: &Manifest,
+    ) -> Vec<TokenStream> {
+        let fields = manifest.get(path);
+        let mut given = vec![];
+        let mut attrs = vec![];
+        let mut forwarded = vec![];
+
+        for attr in &self.attrs {
+            let mut tokens = TokenStream::new();
+            let mut value = TokenStream::new();
+
+            let (name, attr_name) = match attr {
+                Attribute::Normal(n) => {
+                    n.value.prop_tokenize(&mut value, idents, scopes);
+                    (Ident::new(&to_snake_case(&n.name), Span::call_site()), &n.name)
+                }
+                Attribute::Variable(v) if v.name != ".." => {
+                    v.value.tokenize(&mut value, idents, scopes);
+                    (Ident::new(&to_snake_case(&v.name), Span::call_site()), &v.name)
+                }
+                _ => continue,
+            };
+
+            if let Some(fields) = fields {
+                if fields.fallthrough && !fields.contains(&name) {
+                    forwarded.push(quote! {
+                        (#attr_name, &(#value) as &dyn ::reign::view::AttrValue)
+                    });
+                    continue;
+                }
+            }
+
+            if let (Attribute::Variable(v), Some(fields)) = (attr, fields) {
+                if let (Some(ident), Some(ty)) = (field_ident(&v.value, scopes), fields.ty(&name)) {
+                    idents.forward(ident.clone(), ty.clone());
+                }
+            }
+
+            tokens.append(name.clone());
+            tokens.append(Punct::new(':', Spacing::Alone));
+
+            // Allow passing `T` to an `Option<T>` field
+            if fields.map_or(false, |f| f.is_option(&name)) {
+                tokens.append_all(quote! { ::std::convert::Into::into(#value) });
+            } else {
+                tokens.append_all(value);
+            }
+
+            given.push(name);
+            attrs.push(tokens);
+        }
+
+        if let Some(fields) = fields {
+            for (ident, _) in fields.keys() {
+                if given.contains(&ident) {
+                    continue;
+                }
+
+                if self.has_spread() {
+                    attrs.push(quote! { #ident: ::std::clone::Clone::clone(&__reign_props.#ident) });
+                } else if fields.is_optional(&ident) {
+                    let value = fields.default_value(&ident);
+
+                    attrs.push(quote! { #ident: #value });
+                }
+            }
+
+            if fields.fallthrough {
+                attrs.push(quote! { attrs: ::reign::view::Attrs::new(&[#(#forwarded),*]) });
+            }
+        }
+
+        attrs
+    }
+
+    fn end_tokens(&self) -> TokenStream {
+        if !VOID_TAGS.contains(&self.name.as_str()) {
+            let end_tag = LitStr::new(&format!("</{}>", &self.name), Span::call_site());
+
+            quote! {
+                f.write_str(#end_tag)?;
+            }
+        } else {
+            quote! {}
+        }
+    }
+}
+
+fn slot_tokens(name: &str, children: Vec<TokenStream>) -> TokenStream {
+    let name = LitStr::new(name, Span::call_site());
+
+    quote! {
+        (#name, &|f: &mut dyn std::fmt::Write| -> std::fmt::Result {
+            #(#children)*
+            Ok(())
+        })
+    }
+}
+
+fn attrs_tokens(attrs: &[Attribute], idents: &mut ViewFields, scopes: &ViewFields) -> Vec<TokenStream> {
+    attrs
+        .iter()
+        .map(|x| {
+            let mut ts = TokenStream::new();
+
+            x.tokenize(&mut ts, idents, scopes);
+            ts
+        })
+        .collect()
+}
+
+fn nodes_tokens(nodes: &[Node], idents: &mut ViewFields, scopes: &ViewFields, manifest: &Manifest) -> Vec<TokenStream> {
+    let mut tokens = vec![];
+    let mut iter = nodes.iter();
+    let mut child_option = iter.next();
+
+    while child_option.is_some() {
+        let child = child_option.unwrap();
+
+        if let Node::Element(e) = child {
+            if e.template_name().is_some() {
+                child_option = iter.next();
+                continue;
+            }
+
+            if e.control_attr("if").is_some() {
+                let mut after_if = vec![child];
+                let mut next = iter.next();
+                let (mut has_else, mut has_else_if) = (false, false);
+
+                while next.is_some() {
+                    let sibling = next.unwrap();
+
+                    if let Node::Element(e) = sibling {
+                        if e.template_name().is_some() {
+                            next = iter.next();
+                            continue;
+                        }
+
+                        // If element has `else`, Mark the children to be cleaned
+                        if e.control_attr("else").is_some() {
+                            after_if.push(sibling);
+                            has_else = true;
+                            child_option = iter.next();
+                            break;
+                        }
+
+                        // If element has `else-if`, mark the children to be cleaned even though we have no `else`
+                        if e.control_attr("else-if").is_some() {
+                            has_else_if = true;
+                        } else {
+                            // Otherwise go to the main loop
+                            child_option = next;
+                            break;
+                        }
+                    }
+
+                    after_if.push(sibling);
+                    next = iter.next();
+                }
+
+                after_if = clean_if_else_group(after_if, has_else, has_else_if);
+
+                for i in after_if {
+                    let mut ts = TokenStream::new();
+
+                    i.tokenize(&mut ts, idents, scopes, manifest);
+                    tokens.push(ts);
+                }
+
+                // If at the end, break out
+                if next.is_none() {
+                    break;
+                }
+
+                continue;
+            }
+
+            if e.control_attr("else").is_some() || e.control_attr("else-if").is_some() {
+                idents.errors.push((
+                    Some(e.pos),
+                    "expected `!if` element before `!else` or `!else-if`".to_string(),
+                ));
+            }
+
+            if e.control_attr("for").is_some() {
+                // Text in between the loop and its `!empty` sibling is left out
+                let mut ahead = iter.clone();
+                let mut sibling = ahead.next();
+
+                while sibling.map_or(false, |node| node.is_blank() || matches!(node, Node::Comment(_))) {
+                    sibling = ahead.next();
+                }
+
+                if let Some(Node::Element(empty)) = sibling {
+                    if empty.control_attr("empty").is_some() {
+                        let mut ts = TokenStream::new();
+
+                        e.tokenize_with_empty(&mut ts, idents, scopes, manifest, Some(empty));
+                        tokens.push(ts);
+                        iter = ahead;
+                        child_option = iter.next();
+                        continue;
+                    }
+                }
+            }
+
+            if e.control_attr("empty").is_some() {
+                idents.errors.push((Some(e.pos), "expected `!for` element before `!empty`".to_string()));
+            }
+
+            if e.control_attr("case").is_some() {
+                idents.errors.push((Some(e.pos), "expected `!case` element inside `!match`".to_string()));
+            }
+        }
+
+        let mut ts = TokenStream::new();
+
+        child.tokenize(&mut ts, idents, scopes, manifest);
+        tokens.push(ts);
+        child_option = iter.next();
+    }
+
+    tokens
+}
+
+fn clean_if_else_group(group: Vec<&Node>, has_else: bool, has_else_if: bool) -> Vec<&Node> {
+    if has_else {
+        // Clean completely
+        group
+            .into_iter()
+            .filter(|x| matches!(x, Node::Element(_)))
+            .collect()
+    } else if has_else_if {
+        // Clean only between if and else_if
+        let mut last_element = group
+            .iter()
+            .rev()
+            .position(|x| matches!(x, Node::Element(_)))
+            .unwrap();
+
+        last_element = group.len() - last_element - 1;
+
+        group
+            .into_iter()
+            .enumerate()
+            .filter(|(i, x)| {
+                if *i > last_element {
+                    return true;
+                }
+
+                matches!(x, Node::Element(_))
+            })
+            .map(|(_, x)| x)
+            .collect()
+    } else {
+        group
+    }
+}
+
+impl Tokenize for Text {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let errors = idents.errors.len();
+
+        tokens.append_all(parts_tokens(&self.content, idents, scopes, false, Context::Text));
+        idents.locate(errors, self.pos);
+    }
+}
+
+impl Tokenize for Attribute {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        match self {
+            Attribute::Normal(n) => n.tokenize(tokens, idents, scopes),
+            Attribute::Dynamic(d) => d.tokenize(tokens, idents, scopes),
+            Attribute::Variable(v) => v.tokenize(tokens, idents, scopes),
+            _ => {}
+        };
+    }
+}
+
+impl Tokenize for NormalAttribute {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let start = LitStr::new(&format!(" {}=\"", self.name), Span::call_site());
+        let mut value = TokenStream::new();
+
+        self.value.tokenize(&mut value, idents, scopes, attribute_context(&self.name));
+
+        tokens.append_all(quote! {
+            f.write_str(#start)?;
+            #value
+            f.write_str("\"")?;
+        });
+    }
+}
+
+impl ValueTokens for AttributeValue {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields, context: Context) {
+        if !self.has_expr() {
+            let mut string = self.value().unwrap();
+
+            if string == "\"\"" {
+                string = "".to_string();
+            }
+
+            let value = LitStr::new(&encode_attribute_data(&string), Span::call_site());
+
+            tokens.append_all(quote! {
+                f.write_str(#value)?;
+            });
+        } else if context == Context::Url && self.builds_scheme() {
+            // The scheme is checked once the whole URL is built, out of the text
+            // of the static parts and the percent-encoded values
+            let parts: Vec<TokenStream> = self
+                .parts
+                .iter()
+                .map(|part| match part {
+                    StringPart::Normal(n) => {
+                        let lit = LitStr::new(&decode_references(n), Span::call_site());
+
+                        quote! { f.write_str(#lit)?; }
+                    }
+                    StringPart::Expr(_) => parts_tokens(std::slice::from_ref(part), idents, scopes, false, Context::UrlPart),
+                })
+                .collect();
+            let url = context_tokens(Context::Url);
+
+            tokens.append_all(quote! {
+                {
+                    use ::std::fmt::Write as _;
+
+                    let mut __reign_url = ::std::string::String::new();
+
+                    {
+                        let f = &mut __reign_url;
+                        #(#parts)*
+                    }
+
+                    __reign_escaper.escape(f, #url, &__reign_url)?;
+                }
+            });
+        } else {
+            tokens.append_all(parts_tokens(&self.parts, idents, scopes, true, context));
+        }
+    }
+
+    /// Tokens for the value as an expression when given to a component.
+    fn prop_tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        if let Some(string) = self.value() {
+            let value = LitStr::new(&string, Span::call_site());
+
+            tokens.append_all(quote! { #value });
+        } else {
+            let format_lit = LitStr::new(&"{}".repeat(self.parts.len()), Span::call_site());
+            let parts: Vec<TokenStream> = self
+                .parts
+                .iter()
+                .map(|part| match part {
+                    StringPart::Normal(n) => LitStr::new(n, Span::call_site()).into_token_stream(),
+                    StringPart::Expr(e) => {
+                        let mut ts = TokenStream::new();
+
+                        e.tokenize(&mut ts, idents, scopes);
+                        ts
+                    }
+                })
+                .collect();
+
+            tokens.append_all(quote! {
+                format!(#format_lit, #(#parts),*)
+            });
+        }
+    }
+}
+
+impl Tokenize for DynamicAttribute {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let (prefix, suffix) = (&self.prefix, &self.suffix);
+        let mut name = TokenStream::new();
+        let mut value = TokenStream::new();
+
+        self.name.tokenize(&mut name, idents, scopes);
+        self.value.tokenize(&mut value, idents, scopes);
+
+        // The context is only known once the name is, and the attribute
+        // is left out when the name would end the tag or add attributes
+        tokens.append_all(quote! {
+            {
+                let name = format!("{}{}{}", #prefix, #name, #suffix);
+
+                if ::reign::view::escape::is_attribute_name(&name) {
+                    let context = ::reign::view::escape::attribute_context(&name);
+
+                    (&&&Wrap(&(#value))).attr(f, &name, context, __reign_escaper)?;
+                }
+            }
+        });
+    }
+}
+
+impl Tokenize for VariableAttribute {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let name = LitStr::new(&self.name, Span::call_site());
+        let context = context_tokens(attribute_context(&self.name));
+        let mut value = TokenStream::new();
+
+        self.value.tokenize(&mut value, idents, scopes);
+
+        // Written by `AttrValue` when the value implements it, so that
+        // booleans and `None` can leave the attribute out
+        tokens.append_all(quote! {
+            (&&&Wrap(&(#value))).attr(f, #name, #context, __reign_escaper)?;
+        });
+    }
+}
+
+impl Tokenize for Doctype {
+    fn tokenize(&self, tokens: &mut TokenStream, _: &mut ViewFields, _: &ViewFields) {
+        let doctype_str = LitStr::new(&self.content, Span::call_site());
+
+        tokens.append_all(quote! {
+            f.write_str(#doctype_str)?;
+        });
+    }
+}
+
+impl Tokenize for Comment {
+    fn tokenize(&self, tokens: &mut TokenStream, _: &mut ViewFields, _: &ViewFields) {
+        let content = format!("<!--{}-->", self.content);
+        let comment_str = LitStr::new(&content, Span::call_site());
+
+        tokens.append_all(quote! {
+            f.write_str(#comment_str)?;
+        });
+    }
+}
+
+impl Tokenize for Code {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        match self {
+            Code::For(f) => f.tokenize(tokens, idents, scopes),
+            Code::Case(c) => c.tokenize(tokens, idents, scopes),
+            Code::Let(l) => l.tokenize(tokens, idents, scopes),
+            Code::Expr(e) => e.tokenize(tokens, idents, scopes),
+        }
+    }
+}
+
+/// The field of the view if this code is just a field.
+fn field_ident<'a>(code: &'a Code, scopes: &ViewFields) -> Option<&'a Ident> {
+    if let Code::Expr(Expr::Path(path)) = code {
+        if let Some(ident) = path.path.get_ident() {
+            if !scopes.contains(ident) && !KEYWORDS.contains(&ident.to_string().as_str()) {
+                return Some(ident);
+            }
+        }
+    }
+
+    None
+}
+
+/// Writes the static parts as they are and the expressions escaped
+/// for the context they are in.
+fn parts_tokens(
+    parts: &[StringPart],
+    idents: &mut ViewFields,
+    scopes: &ViewFields,
+    in_attr: bool,
+    context: Context,
+) -> TokenStream {
+    let mut tokens = TokenStream::new();
+
+    for (i, part) in parts.iter().enumerate() {
+        match part {
+            StringPart::Normal(n) => {
+                let string = if in_attr { encode_attribute_data(n) } else { n.clone() };
+                let lit = LitStr::new(&string, Span::call_site());
+
+                tokens.append_all(quote! {
+                    f.write_str(#lit)?;
+                });
+            }
+            StringPart::Expr(e) => {
+                let mut expr_tokens = TokenStream::new();
+                e.tokenize(&mut expr_tokens, idents, scopes);
+
+                // Only the start of a URL can set its scheme
+                let context = context_tokens(match context {
+                    Context::Url if i > 0 => Context::UrlPart,
+                    context => context,
+                });
+
+                tokens.append_all(quote! {
+                    (&&Wrap(&(#expr_tokens))).escape(f, #context, __reign_escaper)?;
+                });
+            }
+        }
+    }
+
+    tokens
+}
+
+fn context_tokens(context: Context) -> TokenStream {
+    let variant = Ident::new(&format!("{:?}", context), Span::call_site());
+
+    quote! { ::reign::view::escape::Context::#variant }
+}
+
+/// Same escaping as `reign::view::escape::attribute` for the static parts of an attribute
+fn encode_attribute_data(value: &str) -> String {
+    value.replace('"', "&#x22;")
+}
+
+/// Merges consecutive writes of static strings into a single `write_str`
+/// call so that the static parts of a template cost one call each.
+fn merge_static(tokens: TokenStream) -> TokenStream {
+    let trees: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut out = TokenStream::new();
+    let mut pending = String::new();
+    let mut i = 0;
+
+    while i < trees.len() {
+        if let Some(string) = static_write(&trees[i..]) {
+            pending.push_str(&string);
+            i += 6;
+            continue;
+        }
+
+        if !pending.is_empty() {
+            let lit = LitStr::new(&pending, Span::call_site());
+
+            out.append_all(quote! {
+                f.write_str(#lit)?;
+            });
+            pending.clear();
+        }
+
+        match &trees[i] {
+            TokenTree::Group(g) => {
+                let mut group = Group::new(g.delimiter(), merge_static(g.stream()));
+
+                group.set_span(g.span());
+                out.append(group);
+            }
+            tree => out.append(tree.clone()),
+        }
+
+        i += 1;
+    }
+
+    if !pending.is_empty() {
+        let lit = LitStr::new(&pending, Span::call_site());
+
+        out.append_all(quote! {
+            f.write_str(#lit)?;
+        });
+    }
+
+    out
+}
+
+/// String written if the tokens start with `f.write_str("...")?;`
+fn static_write(trees: &[TokenTree]) -> Option<String> {
+    match trees {
+        [TokenTree::Ident(f), TokenTree::Punct(dot), TokenTree::Ident(write), TokenTree::Group(args), TokenTree::Punct(question), TokenTree::Punct(semi), ..]
+            if f == "f"
+                && dot.as_char() == '.'
+                && write == "write_str"
+                && args.delimiter() == Delimiter::Parenthesis
+                && question.as_char() == '?'
+                && semi.as_char() == ';' =>
+        {
+            parse2::<LitStr>(args.stream()).ok().map(|lit| lit.value())
+        }
+        _ => None,
+    }
+}
+
+impl<T, P> Tokenize for Punctuated<T, P>
+where
+    T: Tokenize,
+    P: ToTokens,
+{
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let mut iter = self.pairs();
+
+        loop {
+            let item = iter.next();
+
+            if item.is_none() {
+                break;
+            }
+
+            match item.unwrap() {
+                Pair::Punctuated(t, p) => {
+                    t.tokenize(tokens, idents, scopes);
+                    p.to_tokens(tokens);
+                }
+                Pair::End(t) => t.tokenize(tokens, idents, scopes),
+            }
+        }
+    }
+}
+
+impl<T> Tokenize for Option<Box<T>>
+where
+    T: Tokenize,
+{
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        if self.is_some() {
+            self.as_ref().unwrap().tokenize(tokens, idents, scopes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_merge_static() {
+        let tokens = merge_static(quote! {
+            f.write_str("<p")?;
+            f.write_str(">")?;
+            if self.a {
+                f.write_str("a")?;
+                f.write_str("b")?;
+            }
+            ::reign::view::escape::text(f, &(self.b))?;
+            f.write_str("</p>")?;
+        });
+
+        assert_eq!(
+            tokens.to_string(),
+            quote! {
+                f.write_str("<p>")?;
+                if self.a {
+                    f.write_str("ab")?;
+                }
+                ::reign::view::escape::text(f, &(self.b))?;
+                f.write_str("</p>")?;
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_inherit_attrs() {
+        let fields = |data: &str| {
+            let template = reign_view_ast::parse::parse(data.to_string(), "Card".to_string(), "").unwrap();
+
+            tokenize(
+                &template,
+                &Manifest::new(quote! { views }),
+                None,
+                &quote! { ::reign::view::HtmlEscaper },
+                &quote! { ::reign::view::StdFilters },
+            )
+            .1
+        };
+
+        let button = fields("<template><b class=\"a\" !inherit-attrs :..=\"attrs\"></b></template>");
+
+        assert!(button.fallthrough);
+        assert_eq!(button.errors.len(), 1);
+        assert_eq!(button.errors[0].1, "`attrs` is the field of the attributes forwarded with `!inherit-attrs`");
+
+        assert!(!fields("<template><b :..=\"extra\"></b></template>").fallthrough);
+
+        let card = fields("<template><shared:button !inherit-attrs /></template>");
+
+        assert_eq!(card.errors[0].1, "expected an html element with `!inherit-attrs`");
+    }
+
+    #[test]
+    fn test_expression_scopes() {
+        let expr: Expr = syn::parse_str(
+            "match a { Some(n) if *n > b => items.iter().map(|i| i + n).sum(), _ => { let c = d; S { c, e } } }",
+        )
+        .unwrap();
+        let mut tokens = TokenStream::new();
+        let mut idents = ViewFields::new();
+
+        expr.tokenize(&mut tokens, &mut idents, &ViewFields::new());
+
+        let names: Vec<String> = idents.keys().into_iter().map(|(ident, _)| ident.to_string()).collect();
+
+        assert_eq!(names, vec!["a", "b", "d", "e", "items"]);
+        assert_eq!(
+            tokens.to_string(),
+            quote! {
+                match self.a {
+                    Some(n) if *n > self.b => self.items.iter().map(|i| i + n).sum(),
+                    _ => { let c = self.d; S { c, e: self.e } },
+                }
+            }
+            .to_string()
+        );
+
+        let expr: Expr = syn::parse_str("if let Some(x) = x { x } else { x }").unwrap();
+        let mut tokens = TokenStream::new();
+
+        expr.tokenize(&mut tokens, &mut ViewFields::new(), &ViewFields::new());
+        assert_eq!(
+            tokens.to_string(),
+            quote! { if let Some(x) = &(self.x) { x } else { self.x } }.to_string()
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::{token::{Comma, Dot2}, Ident, Member, RangeLimits};
+
+use reign_view_ast::expr::*;
+
+use super::{declared, Tokenize, ViewFields};
+
+impl Tokenize for Expr {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        match self {
+            Expr::Array(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Binary(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Block(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Call(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Cast(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Closure(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Field(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Filter(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Group(e) => e.tokenize(tokens, idents, scopes),
+            Expr::If(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Index(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Let(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Match(e) => e.tokenize(tokens, idents, scopes),
+            Expr::MethodCall(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Message(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Paren(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Path(path) => {
+                if let Some(ident) = path.path.get_ident() {
+                    if !scopes.contains(ident) && !KEYWORDS.contains(&ident.to_string().as_str()) {
+                        idents.push(ident.clone());
+                        tokens.append_all(quote! {
+                            self.#ident
+                        });
+                    } else {
+                        ident.to_tokens(tokens);
+                    }
+                } else {
+                    path.to_tokens(tokens);
+                }
+            }
+            Expr::Range(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Reference(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Repeat(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Struct(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Tuple(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Type(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Unary(e) => e.tokenize(tokens, idents, scopes),
+            Expr::Macro(e) => e.to_tokens(tokens),
+            Expr::Lit(e) => e.to_tokens(tokens),
+            Expr::Loop(e) => {
+                let ident = Ident::new("loop", e.span);
+
+                if !scopes.contains(&ident) {
+                    idents.error("`loop` can only be used inside of a `!for` element".to_string());
+                }
+
+                tokens.append(Ident::new("__reign_loop", e.span));
+            }
+        };
+    }
+}
+
+impl Tokenize for ExprArray {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.bracket_token.surround(tokens, |tokens| {
+            self.elems.tokenize(tokens, idents, scopes);
+        })
+    }
+}
+
+impl Tokenize for ExprBinary {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.left.tokenize(tokens, idents, scopes);
+        self.op.to_tokens(tokens);
+        self.right.tokenize(tokens, idents, scopes);
+    }
+}
+
+impl Tokenize for ExprBlock {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let mut scopes = scopes.clone();
+
+        self.brace_token.surround(tokens, |tokens| {
+            for stmt in &self.stmts {
+                match stmt {
+                    Stmt::Local(local) => {
+                        let mut declared = ViewFields::new();
+
+                        local.let_token.to_tokens(tokens);
+                        local.pat.tokenize(tokens, &mut declared, &scopes);
+
+                        if let Some((colon_token, ty)) = &local.ty {
+                            colon_token.to_tokens(tokens);
+                            ty.to_tokens(tokens);
+                        }
+
+                        // The bindings are not visible in their own value
+                        if let Some((eq_token, expr)) = &local.init {
+                            eq_token.to_tokens(tokens);
+                            expr.tokenize(tokens, idents, &scopes);
+                        }
+
+                        local.semi_token.to_tokens(tokens);
+                        scopes.append(declared);
+                    }
+                    Stmt::Expr(expr, semi_token) => {
+                        expr.tokenize(tokens, idents, &scopes);
+                        semi_token.to_tokens(tokens);
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl Tokenize for ExprCall {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.func.tokenize(tokens, idents, scopes);
+        self.paren_token.surround(tokens, |tokens| {
+            self.args.tokenize(tokens, idents, scopes);
+        })
+    }
+}
+
+impl Tokenize for ExprCast {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        // Can't use casting for type identification because it tries to copy the variable
+
+        self.expr.tokenize(tokens, idents, scopes);
+        self.as_token.to_tokens(tokens);
+        self.ty.to_tokens(tokens);
+    }
+}
+
+impl Tokenize for ExprClosure {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let mut declared = ViewFields::new();
+
+        self.move_token.to_tokens(tokens);
+        self.or1_token.to_tokens(tokens);
+
+        for pair in self.inputs.pairs() {
+            let arg = pair.value();
+
+            arg.pat.tokenize(tokens, &mut declared, scopes);
+
+            if let Some((colon_token, ty)) = &arg.ty {
+                colon_token.to_tokens(tokens);
+                ty.to_tokens(tokens);
+            }
+
+            pair.punct().to_tokens(tokens);
+        }
+
+        self.or2_token.to_tokens(tokens);
+
+        if let Some((arrow_token, ty)) = &self.output {
+            arrow_token.to_tokens(tokens);
+            ty.to_tokens(tokens);
+        }
+
+        let mut scopes = scopes.clone();
+
+        scopes.append(declared);
+        self.body.tokenize(tokens, idents, &scopes);
+    }
+}
+
+impl Tokenize for ExprField {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.base.tokenize(tokens, idents, scopes);
+        self.dot_token.to_tokens(tokens);
+        self.member.to_tokens(tokens);
+    }
+}
+
+impl Tokenize for FieldValue {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        if let Some(colon_token) = &self.colon_token {
+            self.member.to_tokens(tokens);
+            colon_token.to_tokens(tokens);
+            self.expr.tokenize(tokens, idents, scopes);
+        } else {
+            // Member is always named
+            if let Member::Named(ident) = &self.member {
+                if scopes.contains(ident) {
+                    ident.to_tokens(tokens);
+                } else {
+                    idents.push(ident.clone());
+                    tokens.append_all(quote! {
+                        #ident: self.#ident
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl Tokenize for ExprFilter {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let name = &self.name;
+        let mut expr = TokenStream::new();
+        let mut args = TokenStream::new();
+
+        self.expr.tokenize(&mut expr, idents, scopes);
+        self.args.tokenize(&mut args, idents, scopes);
+
+        let args = if self.args.is_empty() {
+            quote! {}
+        } else {
+            quote! { , #args }
+        };
+
+        tokens.extend(quote! {
+            __reign_filters.#name(&(#expr) #args)
+        });
+    }
+}
+
+impl Tokenize for ExprGroup {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.group_token.surround(tokens, |tokens| {
+            self.expr.tokenize(tokens, idents, scopes);
+        });
+    }
+}
+
+impl Tokenize for ExprIf {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.if_token.to_tokens(tokens);
+        self.cond.tokenize(tokens, idents, scopes);
+
+        // The bindings of `if let` are only visible in the first branch
+        match &*self.cond {
+            Expr::Let(cond) => {
+                let mut scopes = scopes.clone();
+
+                scopes.append(declared(&cond.pat));
+                self.then_branch.tokenize(tokens, idents, &scopes);
+            }
+            _ => self.then_branch.tokenize(tokens, idents, scopes),
+        }
+
+        if let Some((else_token, branch)) = &self.else_branch {
+            else_token.to_tokens(tokens);
+            branch.tokenize(tokens, idents, scopes);
+        }
+    }
+}
+
+impl Tokenize for ExprIndex {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.expr.tokenize(tokens, idents, scopes);
+
+        self.bracket_token.surround(tokens, |tokens| {
+            self.index.tokenize(tokens, idents, scopes);
+        });
+    }
+}
+
+impl Tokenize for ExprMatch {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let mut expr = TokenStream::new();
+
+        self.match_token.to_tokens(tokens);
+        self.expr.tokenize(&mut expr, idents, scopes);
+        tokens.extend(expr);
+
+        self.brace_token.surround(tokens, |tokens| {
+            for arm in &self.arms {
+                let mut scopes = scopes.clone();
+
+                arm.case.tokenize(tokens, idents, &scopes);
+                arm.fat_arrow_token.to_tokens(tokens);
+                scopes.append(declared(&arm.case.pats));
+                arm.body.tokenize(tokens, idents, &scopes);
+                Comma::default().to_tokens(tokens);
+            }
+        });
+    }
+}
+
+impl Tokenize for ExprMessage {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let key = &self.key;
+        let mut names = vec![];
+        let mut values = vec![];
+
+        for (name, value) in &self.args {
+            let mut ts = TokenStream::new();
+
+            value.tokenize(&mut ts, idents, scopes);
+            names.push(name.to_string());
+            values.push(ts);
+        }
+
+        idents.messages.push((None, key.value(), names.clone()));
+
+        tokens.append_all(quote! {
+            ::reign::view::i18n::Message::new(
+                __reign_catalog,
+                __reign_locale,
+                #key,
+                &[#((#names, &(#values) as &dyn ::std::fmt::Display)),*],
+            )
+        });
+    }
+}
+
+impl Tokenize for ExprMethodCall {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.receiver.tokenize(tokens, idents, scopes);
+        self.dot_token.to_tokens(tokens);
+        self.method.to_tokens(tokens);
+        self.turbofish.to_tokens(tokens);
+
+        self.paren_token.surround(tokens, |tokens| {
+            self.args.tokenize(tokens, idents, scopes);
+        });
+    }
+}
+
+impl Tokenize for ExprParen {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.paren_token.surround(tokens, |tokens| {
+            self.expr.tokenize(tokens, idents, scopes);
+        });
+    }
+}
+
+impl Tokenize for ExprRange {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.from.tokenize(tokens, idents, scopes);
+
+        match &self.limits {
+            RangeLimits::HalfOpen(t) => t.to_tokens(tokens),
+            RangeLimits::Closed(t) => t.to_tokens(tokens),
+        }
+
+        self.to.tokenize(tokens, idents, scopes);
+    }
+}
+
+impl Tokenize for ExprReference {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.and_token.to_tokens(tokens);
+        self.expr.tokenize(tokens, idents, scopes);
+    }
+}
+
+impl Tokenize for ExprRepeat {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.bracket_token.surround(tokens, |tokens| {
+            self.expr.tokenize(tokens, idents, scopes);
+            self.semi_token.to_tokens(tokens);
+            self.len.tokenize(tokens, idents, scopes);
+        });
+    }
+}
+
+impl Tokenize for ExprStruct {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.path.to_tokens(tokens);
+
+        self.brace_token.surround(tokens, |tokens| {
+            self.fields.tokenize(tokens, idents, scopes);
+
+            if self.rest.is_some() {
+                match self.dot2_token {
+                    Some(t) => t.to_tokens(tokens),
+                    None => Dot2::default().to_tokens(tokens),
+                }
+
+                self.rest.tokenize(tokens, idents, scopes);
+            }
+        })
+    }
+}
+
+impl Tokenize for ExprTuple {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.paren_token.surround(tokens, |tokens| {
+            self.elems.tokenize(tokens, idents, scopes);
+
+            // If we only have one argument, we need a trailing comma to
+            // distinguish ExprTuple from ExprParen.
+            if self.elems.len() == 1 && !self.elems.trailing_punct() {
+                Comma::default().to_tokens(tokens);
+            }
+        })
+    }
+}
+
+impl Tokenize for ExprType {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let mut ty_tokens = TokenStream::new();
+
+        self.ty.to_tokens(&mut ty_tokens);
+
+        if let Expr::Path(path) = &*self.expr {
+            if let Some(ident) = path.path.get_ident() {
+                if !scopes.contains(ident) {
+                    idents.insert(ident.clone(), Some(ty_tokens));
+
+                    if let Some((_, default)) = &self.default {
+                        let mut default_idents = ViewFields::new();
+                        let mut default_tokens = TokenStream::new();
+
+                        default.tokenize(&mut default_tokens, &mut default_idents, scopes);
+
+                        if !default_idents.is_empty() {
+                            idents.error(format!(
+                                "default value of `{}` can not use other fields",
+                                ident
+                            ));
+                        } else {
+                            idents.insert_default(ident.clone(), default_tokens);
+                        }
+                    }
+
+                    tokens.append_all(quote! {
+                        self.#ident
+                    });
+                    return;
+                }
+            }
+        }
+
+        self.expr.tokenize(tokens, idents, scopes);
+    }
+}
+
+impl Tokenize for ExprUnary {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.op.to_tokens(tokens);
+        self.expr.tokenize(tokens, idents, scopes);
+    }
+}
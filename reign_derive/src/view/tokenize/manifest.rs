@@ -0,0 +1,36 @@
+use super::ViewFields;
+use proc_macro2::TokenStream;
+use std::collections::HashMap;
+use syn::Ident;
+
+/// Fields of all the views built by a single `views!` call, keyed by
+/// their path in the views module (e.g. `shared::Button`).
+pub struct Manifest {
+    /// Path of the views module used to refer to the components.
+    pub path: TokenStream,
+    views: HashMap<String, ViewFields>,
+}
+
+impl Manifest {
+    pub fn new(path: TokenStream) -> Self {
+        Manifest {
+            path,
+            views: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, path: String, fields: ViewFields) {
+        self.views.insert(path, fields);
+    }
+
+    pub fn get(&self, path: &[Ident]) -> Option<&ViewFields> {
+        self.views.get(&key(path))
+    }
+}
+
+pub fn key(path: &[Ident]) -> String {
+    path.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
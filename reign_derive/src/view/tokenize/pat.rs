@@ -0,0 +1,121 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens, TokenStreamExt};
+
+use reign_view_ast::pat::*;
+
+use super::{Tokenize, ViewFields};
+
+impl Tokenize for Pat {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        match self {
+            Pat::Ident(p) => p.tokenize(tokens, idents, scopes),
+            Pat::Lit(neg, lit) => {
+                neg.to_tokens(tokens);
+                lit.to_tokens(tokens);
+            }
+            Pat::Path(p) => p.to_tokens(tokens),
+            Pat::Reference(p) => p.tokenize(tokens, idents, scopes),
+            Pat::Rest(p) => p.to_tokens(tokens),
+            Pat::Struct(p) => p.tokenize(tokens, idents, scopes),
+            Pat::TupleStruct(p) => p.tokenize(tokens, idents, scopes),
+            Pat::Tuple(p) => p.tokenize(tokens, idents, scopes),
+            Pat::Wild(p) => p.to_tokens(tokens),
+        };
+    }
+}
+
+impl Tokenize for For {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let mut declared = ViewFields::new();
+
+        self.pat.tokenize(tokens, &mut declared, scopes);
+        self.in_token.to_tokens(tokens);
+        self.expr.tokenize(tokens, idents, scopes);
+    }
+}
+
+impl Tokenize for Let {
+    // The value is matched by reference so the bindings borrow from it
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let mut declared = ViewFields::new();
+        let mut expr = TokenStream::new();
+
+        self.let_token.to_tokens(tokens);
+        self.pat.tokenize(tokens, &mut declared, scopes);
+        self.eq_token.to_tokens(tokens);
+        self.expr.tokenize(&mut expr, idents, scopes);
+        tokens.append_all(quote! { &(#expr) });
+    }
+}
+
+impl Tokenize for Case {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let mut declared = ViewFields::new();
+
+        for pair in self.pats.pairs() {
+            pair.value().tokenize(tokens, &mut declared, scopes);
+            pair.punct().to_tokens(tokens);
+        }
+
+        // The bindings of the pattern are visible in the guard
+        if let Some((if_token, expr)) = &self.guard {
+            let mut scopes = scopes.clone();
+
+            scopes.append(declared);
+            if_token.to_tokens(tokens);
+            expr.tokenize(tokens, idents, &scopes);
+        }
+    }
+}
+
+impl Tokenize for FieldPat {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        if let Some(colon_token) = &self.colon_token {
+            self.member.to_tokens(tokens);
+            colon_token.to_tokens(tokens);
+        }
+
+        self.pat.tokenize(tokens, idents, scopes);
+    }
+}
+
+impl Tokenize for PatIdent {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, _: &ViewFields) {
+        self.by_ref.to_tokens(tokens);
+        self.ident.to_tokens(tokens);
+        idents.push(self.ident.clone());
+    }
+}
+
+impl Tokenize for PatReference {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.and_token.to_tokens(tokens);
+        self.pat.tokenize(tokens, idents, scopes);
+    }
+}
+
+impl Tokenize for PatStruct {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.path.to_tokens(tokens);
+
+        self.brace_token.surround(tokens, |tokens| {
+            self.fields.tokenize(tokens, idents, scopes);
+            self.dot2_token.to_tokens(tokens);
+        })
+    }
+}
+
+impl Tokenize for PatTuple {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.paren_token.surround(tokens, |tokens| {
+            self.elems.tokenize(tokens, idents, scopes);
+        });
+    }
+}
+
+impl Tokenize for PatTupleStruct {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.path.to_tokens(tokens);
+        self.pat.tokenize(tokens, idents, scopes);
+    }
+}
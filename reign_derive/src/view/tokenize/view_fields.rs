@@ -0,0 +1,176 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::HashMap;
+use syn::{parse2, Ident, Type};
+
+#[derive(Clone, Default)]
+pub struct ViewFields {
+    pub fields: HashMap<Ident, Option<TokenStream>>,
+    pub defaults: HashMap<Ident, TokenStream>,
+    /// Types of the fields inferred from the components they are passed to.
+    pub forwarded: HashMap<Ident, TokenStream>,
+    /// Errors found in the template along with the position
+    /// of the node they belong to, once known.
+    pub errors: Vec<(Option<usize>, String)>,
+    /// Scope attribute of the template, added to every element it renders.
+    pub scope: Option<String>,
+    /// Whether the template forwards the attributes given to it which are
+    /// not fields with `!inherit-attrs`, which adds the `attrs` field to the view.
+    pub fallthrough: bool,
+    /// Keys and argument names of the `t!` messages in the template, along
+    /// with the position of the node they belong to, once known.
+    pub messages: Vec<(Option<usize>, String, Vec<String>)>,
+}
+
+impl ViewFields {
+    pub fn new() -> Self {
+        ViewFields {
+            fields: HashMap::new(),
+            defaults: HashMap::new(),
+            forwarded: HashMap::new(),
+            errors: vec![],
+            scope: None,
+            fallthrough: false,
+            messages: vec![],
+        }
+    }
+
+    pub fn push(&mut self, ident: Ident) {
+        self.insert(ident, None);
+    }
+
+    pub fn insert(&mut self, ident: Ident, tokens: Option<TokenStream>) {
+        if let Some(ots) = self.fields.get(&ident) {
+            if ots.is_some() && tokens.is_some() {
+                // TODO: Unable to compare the syn::Type or TokenStream here
+                self.error(format!(
+                    "identifier `{}` has multiple type ascription hints",
+                    ident
+                ));
+            } else if ots.is_none() {
+                self.fields.insert(ident, tokens);
+            }
+        } else {
+            self.fields.insert(ident, tokens);
+        }
+    }
+
+    pub fn insert_default(&mut self, ident: Ident, tokens: TokenStream) {
+        if self.defaults.contains_key(&ident) {
+            self.error(format!("identifier `{}` has multiple default values", ident));
+            return;
+        }
+
+        self.defaults.insert(ident, tokens);
+    }
+
+    pub fn error(&mut self, message: String) {
+        self.errors.push((None, message));
+    }
+
+    /// Sets the position of the errors found since `from` and of
+    /// the messages which are not yet attached to a node.
+    pub fn locate(&mut self, from: usize, pos: usize) {
+        for error in self.errors.iter_mut().skip(from) {
+            error.0.get_or_insert(pos);
+        }
+
+        for message in &mut self.messages {
+            message.0.get_or_insert(pos);
+        }
+    }
+
+    /// Type of the field inferred from a component, used only when the
+    /// field has no type ascription in the template.
+    pub fn forward(&mut self, ident: Ident, tokens: TokenStream) {
+        self.forwarded.entry(ident).or_insert(tokens);
+    }
+
+    pub fn ty(&self, ident: &Ident) -> Option<&TokenStream> {
+        match self.fields.get(ident) {
+            Some(Some(ts)) => Some(ts),
+            Some(None) => self.forwarded.get(ident),
+            None => None,
+        }
+    }
+
+    pub fn append(&mut self, other: ViewFields) {
+        for field in other.fields {
+            self.insert(field.0, field.1);
+        }
+
+        for default in other.defaults {
+            self.insert_default(default.0, default.1);
+        }
+
+        self.errors.extend(other.errors);
+    }
+
+    pub fn contains(&self, ident: &Ident) -> bool {
+        self.fields.contains_key(ident)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Fields which can be left out when building the view, either because
+    /// they have a default value or because their type is an `Option`.
+    pub fn is_optional(&self, ident: &Ident) -> bool {
+        self.defaults.contains_key(ident) || self.is_option(ident)
+    }
+
+    pub fn is_option(&self, ident: &Ident) -> bool {
+        if let Some(ts) = self.ty(ident) {
+            if let Ok(Type::Path(ty)) = parse2::<Type>(ts.clone()) {
+                return ty.qself.is_none()
+                    && ty
+                        .path
+                        .segments
+                        .last()
+                        .map_or(false, |s| s.ident == "Option");
+            }
+        }
+
+        false
+    }
+
+    /// Tokens for the value of an optional field when it is left out.
+    pub fn default_value(&self, ident: &Ident) -> TokenStream {
+        if let Some(ts) = self.defaults.get(ident) {
+            ts.clone()
+        } else {
+            quote! { None }
+        }
+    }
+
+    pub fn keys(&self) -> Vec<(Ident, bool)> {
+        self.sorted()
+            .into_iter()
+            .map(|k| (k.clone(), self.ty(k).is_some()))
+            .collect()
+    }
+
+    pub fn values(&self) -> Vec<TokenStream> {
+        self.sorted()
+            .into_iter()
+            .map(|k| {
+                if let Some(ts) = self.ty(k) {
+                    ts.clone()
+                } else {
+                    quote! {
+                        &'a str
+                    }
+                }
+            })
+            .collect()
+    }
+
+    // Sorted by name so that the generated code doesn't depend on hash order
+    fn sorted(&self) -> Vec<&Ident> {
+        let mut fields: Vec<_> = self.fields.keys().collect();
+
+        fields.sort_by_key(|k| k.to_string());
+        fields
+    }
+}
@@ -0,0 +1,108 @@
+//! Renders the views used in the tests and reports the number of heap
+//! allocations and the time taken per render.
+//!
+//! Run with `cargo bench -p reign_view`.
+
+use reign::prelude::*;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+views!("src", "views");
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const ITERATIONS: usize = 100_000;
+
+// `std::hint::black_box` is not available in the minimum supported Rust version
+fn black_box<T>(value: T) -> T {
+    unsafe {
+        let ret = std::ptr::read_volatile(&value);
+        std::mem::forget(value);
+        ret
+    }
+}
+
+fn bench(name: &str, mut render: impl FnMut()) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        render();
+    }
+
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "{:<32} {:>8.2} allocations/render {:>10.0?}/render",
+        name,
+        allocations as f64 / ITERATIONS as f64,
+        elapsed / ITERATIONS as u32,
+    );
+}
+
+fn main() {
+    let page = views::render::Page {
+        count: 3,
+        items: &["Inbox", "Drafts", "Sent <archived>"],
+        title: "Messages",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+    let card = views::slots::Named {
+        title: "Hello",
+        body: "World",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    let mut buffer = String::with_capacity(4096);
+
+    bench("page: render_to reused buffer", || {
+        buffer.clear();
+        page.render_to(&mut buffer).unwrap();
+        black_box(&buffer);
+    });
+
+    bench("page: render_to_string", || {
+        black_box(page.render_to_string());
+    });
+
+    bench("page: to_string", || {
+        black_box(page.to_string());
+    });
+
+    let mut sink = Vec::with_capacity(4096);
+
+    bench("page: write_to io::Write", || {
+        sink.clear();
+        page.write_to(&mut sink).unwrap();
+        black_box(&sink);
+    });
+
+    bench("component with slots: render_to", || {
+        buffer.clear();
+        card.render_to(&mut buffer).unwrap();
+        black_box(&buffer);
+    });
+}
@@ -0,0 +1,250 @@
+use crate::escape::{attribute_context, Context, Escape, Escaper, NoEscaper, Safe, Wrap};
+use std::{
+    borrow::Cow,
+    fmt::{Display, Result, Write},
+};
+
+/// How a value bound to an attribute with `:name="value"` is written.
+///
+/// Values which don't implement this trait are always written as
+/// ` name="value"`. Booleans write the bare name of the attribute when
+/// `true` and nothing when `false`, while `None` omits the attribute.
+///
+/// ```
+/// use reign_view::{AttrValue, Context, Escaper, HtmlEscaper};
+/// use std::fmt::{Result, Write};
+///
+/// // Written as `aria-expanded="true"` instead of the bare name
+/// struct Expanded(bool);
+///
+/// impl AttrValue for Expanded {
+///     fn write_attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result {
+///         write!(f, " {}=\"", name)?;
+///         escaper.escape(f, context, &self.0)?;
+///         f.write_str("\"")
+///     }
+/// }
+///
+/// let mut out = String::new();
+///
+/// Expanded(false).write_attr(&mut out, "aria-expanded", Context::Attribute, &HtmlEscaper).unwrap();
+/// assert_eq!(out, " aria-expanded=\"false\"");
+/// ```
+pub trait AttrValue {
+    /// Writes the attribute with the given name, including the space before
+    /// it, or nothing to omit it. The value is escaped for the context.
+    fn write_attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result;
+}
+
+/// Writes ` name="value"` with the value escaped for the context.
+fn write_value(f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper, value: &dyn Display) -> Result {
+    write!(f, " {}=\"", name)?;
+    escaper.escape(f, context, value)?;
+    f.write_str("\"")
+}
+
+impl AttrValue for bool {
+    fn write_attr(&self, f: &mut dyn Write, name: &str, _: Context, _: &dyn Escaper) -> Result {
+        if *self {
+            write!(f, " {}", name)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: AttrValue> AttrValue for Option<T> {
+    fn write_attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result {
+        match self {
+            Some(value) => value.write_attr(f, name, context, escaper),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: AttrValue + ?Sized> AttrValue for &T {
+    fn write_attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result {
+        (**self).write_attr(f, name, context, escaper)
+    }
+}
+
+impl<T: AttrValue + ?Sized> AttrValue for Box<T> {
+    fn write_attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result {
+        (**self).write_attr(f, name, context, escaper)
+    }
+}
+
+impl<T: Display> AttrValue for Safe<T> {
+    fn write_attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result {
+        write!(f, " {}=\"", name)?;
+        self.escape(f, context, escaper)?;
+        f.write_str("\"")
+    }
+}
+
+macro_rules! impl_attr_value {
+    ($($ty:ty),*) => {
+        $(
+            impl AttrValue for $ty {
+                fn write_attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result {
+                    write_value(f, name, context, escaper, &self)
+                }
+            }
+        )*
+    };
+}
+
+impl_attr_value!(str, String, Cow<'_, str>, char);
+impl_attr_value!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// Value the attribute is written with before escaping, or `None` when it is left out.
+pub(crate) fn raw_value(value: &dyn AttrValue) -> Option<String> {
+    let mut out = String::new();
+
+    value.write_attr(&mut out, "", Context::Attribute, &NoEscaper).ok()?;
+    out.strip_prefix(" =\"")
+        .and_then(|value| value.strip_suffix('"'))
+        .map(str::to_string)
+}
+
+/// Attributes given to a component which are not fields of the component.
+///
+/// A component which writes `!inherit-attrs` on one of its elements gets an
+/// `attrs` field holding them, and the element gets the attributes along
+/// with its own. Its `class` and `style` are merged with those of the element.
+#[derive(Clone, Copy, Default)]
+pub struct Attrs<'a> {
+    attrs: &'a [(&'static str, &'a dyn AttrValue)],
+}
+
+impl<'a> Attrs<'a> {
+    pub fn new(attrs: &'a [(&'static str, &'a dyn AttrValue)]) -> Self {
+        Attrs { attrs }
+    }
+
+    /// Returns the value of the attribute with the given name if it was given.
+    pub fn get(&self, name: &str) -> Option<&'a dyn AttrValue> {
+        self.attrs
+            .iter()
+            .find(|(attr_name, _)| *attr_name == name)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attrs.is_empty()
+    }
+
+    /// Writes the attributes except the ones in `skip`, which were merged
+    /// with the attributes of the element.
+    #[doc(hidden)]
+    pub fn write(&self, f: &mut dyn Write, escaper: &dyn Escaper, skip: &[&str]) -> Result {
+        for (name, value) in self.attrs {
+            if !skip.contains(name) {
+                value.write_attr(f, name, attribute_context(name), escaper)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// The generated views call `(&&&Wrap(&value)).attr(f, name, context, escaper)`
+// which resolves to `ViaAttrValue` when the value implements `AttrValue`,
+// to `ViaAttrEscape` when it implements `Escape` and to `ViaAttrDisplay` otherwise.
+
+#[doc(hidden)]
+pub trait ViaAttrValue {
+    fn attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result;
+}
+
+impl<T: AttrValue + ?Sized> ViaAttrValue for &&Wrap<'_, T> {
+    fn attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result {
+        self.0.write_attr(f, name, context, escaper)
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaAttrEscape {
+    fn attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result;
+}
+
+impl<T: Escape + ?Sized> ViaAttrEscape for &Wrap<'_, T> {
+    fn attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result {
+        write!(f, " {}=\"", name)?;
+        self.0.escape(f, context, escaper)?;
+        f.write_str("\"")
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaAttrDisplay {
+    fn attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result;
+}
+
+impl<T: Display + ?Sized> ViaAttrDisplay for Wrap<'_, T> {
+    fn attr(&self, f: &mut dyn Write, name: &str, context: Context, escaper: &dyn Escaper) -> Result {
+        write_value(f, name, context, escaper, &self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::escape::HtmlEscaper;
+
+    fn attr(value: &dyn AttrValue) -> String {
+        let mut out = String::new();
+
+        value.write_attr(&mut out, "x", Context::Attribute, &HtmlEscaper).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_bool() {
+        assert_eq!(attr(&true), " x");
+        assert_eq!(attr(&false), "");
+        assert_eq!(attr(&Some(true)), " x");
+        assert_eq!(attr(&None::<bool>), "");
+    }
+
+    #[test]
+    fn test_option() {
+        assert_eq!(attr(&Some("a\"b")), " x=\"a&#x22;b\"");
+        assert_eq!(attr(&Some(1u8)), " x=\"1\"");
+        assert_eq!(attr(&None::<&str>), "");
+        assert_eq!(attr(&Some(Safe("<b>"))), " x=\"<b>\"");
+    }
+
+    #[test]
+    fn test_attrs() {
+        let attrs = Attrs::new(&[("id", &"a\"b"), ("hidden", &true), ("class", &"big"), ("title", &None::<&str>)]);
+        let mut out = String::new();
+
+        attrs.write(&mut out, &HtmlEscaper, &["class"]).unwrap();
+        assert_eq!(out, " id=\"a&#x22;b\" hidden");
+        assert_eq!(attrs.get("class").and_then(raw_value), Some("big".to_string()));
+        assert_eq!(attrs.get("title").and_then(raw_value), None);
+        assert!(attrs.get("lang").is_none());
+    }
+
+    // The extra references are what picks the most specific trait
+    #[allow(clippy::needless_borrow)]
+    #[test]
+    fn test_via() {
+        struct Plain;
+
+        impl Display for Plain {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> Result {
+                f.write_str("\"")
+            }
+        }
+
+        let mut out = String::new();
+
+        (&&&Wrap(&false)).attr(&mut out, "a", Context::Attribute, &HtmlEscaper).unwrap();
+        (&&&Wrap(&Plain)).attr(&mut out, "b", Context::Attribute, &HtmlEscaper).unwrap();
+        (&&&Wrap(&Safe(1))).attr(&mut out, "c", Context::Attribute, &HtmlEscaper).unwrap();
+
+        assert_eq!(out, " b=\"&#x22;\" c=\"1\"");
+    }
+}
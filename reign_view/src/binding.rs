@@ -0,0 +1,552 @@
+use crate::{
+    attr::{raw_value, AttrValue},
+    escape::{Context, Escaper, Wrap},
+};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt::{Display, Result, Write},
+    hash::BuildHasher,
+};
+
+/// Class names of an element, collected from its `class` and `:class`
+/// attributes. Duplicate names are only written once.
+#[derive(Clone, Debug, Default)]
+pub struct ClassList<'a> {
+    names: Vec<Cow<'a, str>>,
+}
+
+impl<'a> ClassList<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the whitespace separated class names.
+    pub fn add(&mut self, names: Cow<'a, str>) {
+        match names {
+            Cow::Borrowed(names) => names.split_whitespace().for_each(|name| self.insert(Cow::Borrowed(name))),
+            Cow::Owned(names) => names
+                .split_whitespace()
+                .for_each(|name| self.insert(Cow::Owned(name.to_string()))),
+        }
+    }
+
+    /// Adds the class names of the attribute forwarded with `!inherit-attrs`.
+    #[doc(hidden)]
+    pub fn add_attr(&mut self, value: Option<&dyn AttrValue>) {
+        if let Some(names) = value.and_then(raw_value) {
+            self.add(Cow::Owned(names));
+        }
+    }
+
+    fn insert(&mut self, name: Cow<'a, str>) {
+        if !self.names.contains(&name) {
+            self.names.push(name);
+        }
+    }
+
+    /// Writes the `class` attribute, or nothing if there are no class names.
+    #[doc(hidden)]
+    pub fn write(&self, f: &mut dyn Write, escaper: &dyn Escaper) -> Result {
+        if self.names.is_empty() {
+            return Ok(());
+        }
+
+        f.write_str(" class=\"")?;
+
+        for (i, name) in self.names.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+
+            escaper.escape(f, Context::Attribute, name)?;
+        }
+
+        f.write_str("\"")
+    }
+}
+
+/// Declarations of an element, collected from its `style` and `:style`
+/// attributes. A property given twice keeps its place and takes the last value.
+///
+/// Declarations whose property is not made of letters, digits and `-` or
+/// whose value contains `;`, `{` or `}` outside of strings and brackets are
+/// left out so that a value can't add declarations of its own. Declarations
+/// without a property are kept. The values which don't come from the text of
+/// the template are escaped for the style context, like `{{ value }}` in a
+/// `style` attribute.
+#[derive(Clone, Debug, Default)]
+pub struct StyleList<'a> {
+    declarations: Vec<Declaration<'a>>,
+}
+
+#[derive(Clone, Debug)]
+struct Declaration<'a> {
+    property: Cow<'a, str>,
+    /// Empty for the declarations without a property
+    value: Cow<'a, str>,
+    /// Whether the declaration is written in the template, and so written as it is
+    trusted: bool,
+}
+
+impl<'a> StyleList<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `;` separated declarations, ignoring the `;` inside of
+    /// strings and brackets like in `url(data:image/png;base64,...)`.
+    pub fn add(&mut self, declarations: Cow<'a, str>) {
+        self.add_declarations(declarations, false);
+    }
+
+    /// Adds the declarations of the `style` attribute written in the template,
+    /// whose values are written as they are.
+    #[doc(hidden)]
+    pub fn add_static(&mut self, declarations: Cow<'a, str>) {
+        self.add_declarations(declarations, true);
+    }
+
+    /// Adds the declarations of the attribute forwarded with `!inherit-attrs`.
+    #[doc(hidden)]
+    pub fn add_attr(&mut self, value: Option<&dyn AttrValue>) {
+        if let Some(declarations) = value.and_then(raw_value) {
+            self.add(Cow::Owned(declarations));
+        }
+    }
+
+    /// Sets the value of the property.
+    pub fn set(&mut self, property: Cow<'a, str>, value: Cow<'a, str>) {
+        self.insert(property, value, false);
+    }
+
+    fn add_declarations(&mut self, declarations: Cow<'a, str>, trusted: bool) {
+        match declarations {
+            Cow::Borrowed(declarations) => {
+                for declaration in split_declarations(declarations).0 {
+                    match declaration.split_once(':') {
+                        Some((property, value)) => self.insert(Cow::Borrowed(property), Cow::Borrowed(value), trusted),
+                        None => self.push_verbatim(Cow::Borrowed(declaration), trusted),
+                    }
+                }
+            }
+            Cow::Owned(declarations) => {
+                for declaration in split_declarations(&declarations).0 {
+                    match declaration.split_once(':') {
+                        Some((property, value)) => {
+                            self.insert(Cow::Owned(property.to_string()), Cow::Owned(value.to_string()), trusted)
+                        }
+                        None => self.push_verbatim(Cow::Owned(declaration.to_string()), trusted),
+                    }
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, property: Cow<'a, str>, value: Cow<'a, str>, trusted: bool) {
+        let property = trim(property);
+        let value = trim(value);
+        let valid = !property.is_empty()
+            && property.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && !value.is_empty()
+            && is_single_value(&value);
+
+        if !valid {
+            return;
+        }
+
+        match self
+            .declarations
+            .iter_mut()
+            .find(|d| !d.value.is_empty() && d.property.eq_ignore_ascii_case(&property))
+        {
+            Some(declaration) => {
+                declaration.value = value;
+                declaration.trusted = trusted;
+            }
+            None => self.declarations.push(Declaration { property, value, trusted }),
+        }
+    }
+
+    // Declarations without a property are kept with an empty value
+    fn push_verbatim(&mut self, declaration: Cow<'a, str>, trusted: bool) {
+        let declaration = trim(declaration);
+
+        if declaration.is_empty() || !is_single_value(&declaration) {
+            return;
+        }
+
+        if !self.declarations.iter().any(|d| d.value.is_empty() && d.property == declaration) {
+            self.declarations.push(Declaration {
+                property: declaration,
+                value: Cow::Borrowed(""),
+                trusted,
+            });
+        }
+    }
+
+    /// Writes the `style` attribute, or nothing if there are no declarations.
+    #[doc(hidden)]
+    pub fn write(&self, f: &mut dyn Write, escaper: &dyn Escaper) -> Result {
+        if self.declarations.is_empty() {
+            return Ok(());
+        }
+
+        f.write_str(" style=\"")?;
+
+        for (i, declaration) in self.declarations.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+
+            if declaration.value.is_empty() {
+                declaration.write_value(f, escaper, &declaration.property)?;
+            } else {
+                // Only made of letters, digits and `-`
+                f.write_str(&declaration.property)?;
+                f.write_str(": ")?;
+                declaration.write_value(f, escaper, &declaration.value)?;
+            }
+        }
+
+        f.write_str("\"")
+    }
+}
+
+impl Declaration<'_> {
+    fn write_value(&self, f: &mut dyn Write, escaper: &dyn Escaper, value: &str) -> Result {
+        if self.trusted {
+            f.write_str(&value.replace('"', "&#x22;"))
+        } else {
+            escaper.escape(f, Context::Style, &value)
+        }
+    }
+}
+
+/// Splits the declarations on the `;` outside of strings and brackets, along
+/// with whether every string and bracket is closed and there is no `{` or `}`
+/// outside of strings.
+fn split_declarations(declarations: &str) -> (Vec<&str>, bool) {
+    let (mut pieces, mut start) = (vec![], 0);
+    let (mut depth, mut quote, mut escaped, mut valid) = (0_usize, None, false, true);
+
+    for (i, c) in declarations.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match (quote, c) {
+            (_, '\\') => escaped = true,
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '(') | (None, '[') => depth += 1,
+            (None, ')') | (None, ']') => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => valid = false,
+            },
+            (None, '{') | (None, '}') => valid = false,
+            (None, ';') if depth == 0 => {
+                pieces.push(&declarations[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    pieces.push(&declarations[start..]);
+    (pieces, valid && depth == 0 && quote.is_none() && !escaped)
+}
+
+// A value which can't end its declaration or start a rule
+fn is_single_value(value: &str) -> bool {
+    let (pieces, valid) = split_declarations(value);
+
+    valid && pieces.len() == 1
+}
+
+fn trim(s: Cow<str>) -> Cow<str> {
+    match s {
+        Cow::Borrowed(s) => Cow::Borrowed(s.trim()),
+        Cow::Owned(s) if s.trim().len() == s.len() => Cow::Owned(s),
+        Cow::Owned(s) => Cow::Owned(s.trim().to_string()),
+    }
+}
+
+/// Values which can be bound to the `class` attribute with `:class`.
+///
+/// Strings add their whitespace separated class names, `(name, bool)` pairs
+/// add the name when the boolean is `true` and maps add the keys whose value
+/// is `true`. Arrays, slices, vectors and options add the names of their items.
+/// Values of other types are written with [`std::fmt::Display`].
+pub trait ClassValue {
+    fn add_to<'a>(&'a self, list: &mut ClassList<'a>);
+}
+
+impl ClassValue for str {
+    fn add_to<'a>(&'a self, list: &mut ClassList<'a>) {
+        list.add(Cow::Borrowed(self));
+    }
+}
+
+impl ClassValue for String {
+    fn add_to<'a>(&'a self, list: &mut ClassList<'a>) {
+        list.add(Cow::Borrowed(self));
+    }
+}
+
+impl ClassValue for Cow<'_, str> {
+    fn add_to<'a>(&'a self, list: &mut ClassList<'a>) {
+        list.add(Cow::Borrowed(self));
+    }
+}
+
+impl<T: ClassValue> ClassValue for (T, bool) {
+    fn add_to<'a>(&'a self, list: &mut ClassList<'a>) {
+        if self.1 {
+            self.0.add_to(list);
+        }
+    }
+}
+
+impl<K: ClassValue, S: BuildHasher> ClassValue for HashMap<K, bool, S> {
+    fn add_to<'a>(&'a self, list: &mut ClassList<'a>) {
+        self.iter().filter(|(_, on)| **on).for_each(|(name, _)| name.add_to(list));
+    }
+}
+
+impl<K: ClassValue> ClassValue for BTreeMap<K, bool> {
+    fn add_to<'a>(&'a self, list: &mut ClassList<'a>) {
+        self.iter().filter(|(_, on)| **on).for_each(|(name, _)| name.add_to(list));
+    }
+}
+
+/// Values which can be bound to the `style` attribute with `:style`.
+///
+/// Strings add their `;` separated declarations, while `(property, value)`
+/// pairs and maps from properties to values add one declaration each.
+/// Arrays, slices, vectors and options add the declarations of their items.
+/// Values of other types are written with [`std::fmt::Display`].
+pub trait StyleValue {
+    fn add_to<'a>(&'a self, list: &mut StyleList<'a>);
+}
+
+impl StyleValue for str {
+    fn add_to<'a>(&'a self, list: &mut StyleList<'a>) {
+        list.add(Cow::Borrowed(self));
+    }
+}
+
+impl StyleValue for String {
+    fn add_to<'a>(&'a self, list: &mut StyleList<'a>) {
+        list.add(Cow::Borrowed(self));
+    }
+}
+
+impl StyleValue for Cow<'_, str> {
+    fn add_to<'a>(&'a self, list: &mut StyleList<'a>) {
+        list.add(Cow::Borrowed(self));
+    }
+}
+
+impl<K: AsRef<str>, V: Display> StyleValue for (K, V) {
+    fn add_to<'a>(&'a self, list: &mut StyleList<'a>) {
+        list.set(Cow::Borrowed(self.0.as_ref()), Cow::Owned(self.1.to_string()));
+    }
+}
+
+impl<K: AsRef<str>, V: Display, S: BuildHasher> StyleValue for HashMap<K, V, S> {
+    fn add_to<'a>(&'a self, list: &mut StyleList<'a>) {
+        for (property, value) in self {
+            list.set(Cow::Borrowed(property.as_ref()), Cow::Owned(value.to_string()));
+        }
+    }
+}
+
+impl<K: AsRef<str>, V: Display> StyleValue for BTreeMap<K, V> {
+    fn add_to<'a>(&'a self, list: &mut StyleList<'a>) {
+        for (property, value) in self {
+            list.set(Cow::Borrowed(property.as_ref()), Cow::Owned(value.to_string()));
+        }
+    }
+}
+
+// Implementations shared by both traits for the containers
+macro_rules! impl_containers {
+    ($trait:ident, $list:ident) => {
+        impl<T: $trait + ?Sized> $trait for &T {
+            fn add_to<'a>(&'a self, list: &mut $list<'a>) {
+                (**self).add_to(list);
+            }
+        }
+
+        impl<T: $trait + ?Sized> $trait for Box<T> {
+            fn add_to<'a>(&'a self, list: &mut $list<'a>) {
+                (**self).add_to(list);
+            }
+        }
+
+        impl<T: $trait> $trait for Option<T> {
+            fn add_to<'a>(&'a self, list: &mut $list<'a>) {
+                if let Some(value) = self {
+                    value.add_to(list);
+                }
+            }
+        }
+
+        impl<T: $trait> $trait for [T] {
+            fn add_to<'a>(&'a self, list: &mut $list<'a>) {
+                self.iter().for_each(|value| value.add_to(list));
+            }
+        }
+
+        impl<T: $trait, const N: usize> $trait for [T; N] {
+            fn add_to<'a>(&'a self, list: &mut $list<'a>) {
+                self[..].add_to(list);
+            }
+        }
+
+        impl<T: $trait> $trait for Vec<T> {
+            fn add_to<'a>(&'a self, list: &mut $list<'a>) {
+                self[..].add_to(list);
+            }
+        }
+    };
+}
+
+impl_containers!(ClassValue, ClassList);
+impl_containers!(StyleValue, StyleList);
+
+// The generated views call `(&&Wrap(&value)).add_class(&mut list)` which resolves
+// to `ViaClassValue` when the value implements `ClassValue` and to
+// `ViaClassDisplay` otherwise, and the same for `add_style`.
+
+#[doc(hidden)]
+pub trait ViaClassValue<'a> {
+    fn add_class(&self, list: &mut ClassList<'a>);
+}
+
+impl<'a, T: ClassValue + ?Sized> ViaClassValue<'a> for &Wrap<'a, T> {
+    fn add_class(&self, list: &mut ClassList<'a>) {
+        self.0.add_to(list);
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaClassDisplay<'a> {
+    fn add_class(&self, list: &mut ClassList<'a>);
+}
+
+impl<'a, T: Display + ?Sized> ViaClassDisplay<'a> for Wrap<'a, T> {
+    fn add_class(&self, list: &mut ClassList<'a>) {
+        list.add(Cow::Owned(self.0.to_string()));
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaStyleValue<'a> {
+    fn add_style(&self, list: &mut StyleList<'a>);
+}
+
+impl<'a, T: StyleValue + ?Sized> ViaStyleValue<'a> for &Wrap<'a, T> {
+    fn add_style(&self, list: &mut StyleList<'a>) {
+        self.0.add_to(list);
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaStyleDisplay<'a> {
+    fn add_style(&self, list: &mut StyleList<'a>);
+}
+
+impl<'a, T: Display + ?Sized> ViaStyleDisplay<'a> for Wrap<'a, T> {
+    fn add_style(&self, list: &mut StyleList<'a>) {
+        list.add(Cow::Owned(self.0.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::escape::HtmlEscaper;
+    use maplit::btreemap;
+
+    fn classes(value: &dyn ClassValue) -> String {
+        let mut list = ClassList::new();
+        let mut out = String::new();
+
+        list.add(Cow::Borrowed("btn  active"));
+        value.add_to(&mut list);
+        list.write(&mut out, &HtmlEscaper).unwrap();
+        out
+    }
+
+    fn styles(value: &dyn StyleValue) -> String {
+        let mut list = StyleList::new();
+        let mut out = String::new();
+
+        list.add(Cow::Borrowed("color: red; margin: 0"));
+        value.add_to(&mut list);
+        list.write(&mut out, &HtmlEscaper).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_class() {
+        assert_eq!(classes(&"big active"), " class=\"btn active big\"");
+        assert_eq!(classes(&[("wide", true), ("active", false), ("tall", true)]), " class=\"btn active wide tall\"");
+        assert_eq!(classes(&btreemap! { "b" => true, "a" => true, "c" => false }), " class=\"btn active a b\"");
+        assert_eq!(classes(&vec![Some("x\""), None]), " class=\"btn active x&#x22;\"");
+
+        let mut out = String::new();
+
+        ClassList::new().write(&mut out, &HtmlEscaper).unwrap();
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn test_style() {
+        assert_eq!(styles(&"color: blue"), " style=\"color: blue; margin: 0\"");
+        assert_eq!(styles(&[("width", "10px"), ("margin", "auto")]), " style=\"color: red; margin: auto; width: 10px\"");
+        assert_eq!(styles(&btreemap! { "top" => 1 }), " style=\"color: red; margin: 0; top: 1\"");
+        assert_eq!(
+            styles(&[("color", "blue; position: fixed"), ("b\"", "x"), ("font", "\"a\"")]),
+            " style=\"color: red; margin: 0; font: \\22 a\\22 \""
+        );
+        assert_eq!(
+            styles(&"content: \";\"; --empty; color: blue"),
+            " style=\"color: blue; margin: 0; content: \\22 \\3B \\22 ; \\2D \\2D empty\""
+        );
+        assert_eq!(
+            styles(&[("content", "\"}\""), ("width", "calc(1px"), ("top", "a) }")]),
+            " style=\"color: red; margin: 0; content: \\22 \\7D \\22 \""
+        );
+
+        let mut list = StyleList::new();
+        let mut out = String::new();
+
+        list.add_static(Cow::Borrowed("background:url(data:image/png;base64,iVBO==); content: \";\"; --empty"));
+        list.add(Cow::Borrowed("top: 1px"));
+        list.write(&mut out, &HtmlEscaper).unwrap();
+
+        assert_eq!(
+            out,
+            " style=\"background: url(data:image/png;base64,iVBO==); content: &#x22;;&#x22;; --empty; top: 1px\""
+        );
+    }
+
+    // The extra references are what picks `ViaClassValue` over `ViaClassDisplay`
+    #[allow(clippy::needless_borrow)]
+    #[test]
+    fn test_via() {
+        let mut list = ClassList::new();
+        let mut out = String::new();
+
+        (&&Wrap(&["a", "b"])).add_class(&mut list);
+        (&&Wrap(&3)).add_class(&mut list);
+        list.write(&mut out, &HtmlEscaper).unwrap();
+
+        assert_eq!(out, " class=\"a b 3\"");
+    }
+}
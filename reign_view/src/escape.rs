@@ -0,0 +1,592 @@
+use std::{
+    borrow::Cow,
+    fmt::{Display, Result, Write},
+};
+
+/// Part of the page a value is written into, which decides how it is escaped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Context {
+    /// Content of an element
+    Text,
+    /// Value of a double quoted attribute
+    Attribute,
+    /// Start of a URL attribute like `href`, whose scheme is checked
+    Url,
+    /// Rest of a URL attribute, which is percent-encoded
+    UrlPart,
+    /// Content of a `<script>` element or an `on*` attribute
+    Script,
+    /// Content of a `<style>` element or a `style` attribute
+    Style,
+}
+
+// Attributes whose value is a URL
+const URL_ATTRIBUTES: [&str; 12] = [
+    "href",
+    "src",
+    "action",
+    "formaction",
+    "cite",
+    "poster",
+    "background",
+    "data",
+    "codebase",
+    "longdesc",
+    "manifest",
+    "xlink:href",
+];
+
+// Schemes allowed at the start of URL attributes
+const URL_SCHEMES: [&str; 4] = ["http", "https", "mailto", "tel"];
+
+// Written instead of a URL whose scheme is not allowed
+const BLOCKED_URL: &str = "about:invalid#blocked";
+
+// Named character references which can spell the scheme of a URL or its end
+const NAMED_REFERENCES: [(&str, char); 12] = [
+    ("Tab", '\t'),
+    ("NewLine", '\n'),
+    ("colon", ':'),
+    ("sol", '/'),
+    ("quest", '?'),
+    ("num", '#'),
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{A0}'),
+];
+
+/// Context of the value of the attribute with the given name.
+#[doc(hidden)]
+pub fn attribute_context(name: &str) -> Context {
+    let name = name.to_ascii_lowercase();
+
+    if name.starts_with("on") {
+        Context::Script
+    } else if name == "style" {
+        Context::Style
+    } else if URL_ATTRIBUTES.contains(&name.as_str()) {
+        Context::Url
+    } else {
+        Context::Attribute
+    }
+}
+
+/// Whether the name can be written as the name of an attribute, which
+/// is checked for the names built with `:prefix{{ name }}suffix`.
+#[doc(hidden)]
+pub fn is_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '>' | '<' | '/' | '=' | '&' | '`')
+        })
+}
+
+/// Writer adapter which escapes the characters that are special in the
+/// given context before passing the rest on unchanged.
+struct EscapeWriter<'a> {
+    inner: &'a mut dyn Write,
+    context: Context,
+    /// Whether every ASCII character except the alphanumeric ones is
+    /// encoded in the content of elements and in attributes
+    strict: bool,
+}
+
+impl Write for EscapeWriter<'_> {
+    fn write_str(&mut self, s: &str) -> Result {
+        let mut start = 0;
+
+        for (i, c) in s.char_indices() {
+            if self.strict && is_markup(self.context) && c.is_ascii() && !c.is_ascii_alphanumeric() {
+                self.inner.write_str(&s[start..i])?;
+                write!(self.inner, "&#x{:02X};", c as u32)?;
+                start = i + 1;
+            } else if let Some(entity) = entity(self.context, c) {
+                self.inner.write_str(&s[start..i])?;
+                self.inner.write_str(entity)?;
+                start = i + c.len_utf8();
+            } else if needs_encoding(self.context, c) {
+                self.inner.write_str(&s[start..i])?;
+                encode(self.inner, self.context, c)?;
+                start = i + c.len_utf8();
+            }
+        }
+
+        self.inner.write_str(&s[start..])
+    }
+}
+
+// Contexts in which HTML character references are decoded
+fn is_markup(context: Context) -> bool {
+    matches!(context, Context::Text | Context::Attribute | Context::Url)
+}
+
+// Based on https://cheatsheetseries.owasp.org/cheatsheets/Cross_Site_Scripting_Prevention_Cheat_Sheet.html#rule-1-html-encode-before-inserting-untrusted-data-into-html-element-content
+fn entity(context: Context, c: char) -> Option<&'static str> {
+    match (context, c) {
+        (Context::Text, '&') => Some("&amp;"),
+        (Context::Text, '<') => Some("&lt;"),
+        (Context::Text, '>') => Some("&gt;"),
+        (Context::Text, '\'') => Some("&#x27;"),
+        (Context::Text, '"') => Some("&quot;"),
+        (Context::Attribute, '"') | (Context::Url, '"') => Some("&#x22;"),
+        // Keeps the value from writing character references of its own
+        (Context::Attribute, '&') | (Context::Url, '&') => Some("&amp;"),
+        _ => None,
+    }
+}
+
+// Rules 3, 4 and 5 of the same cheatsheet, everything except
+// alphanumeric characters is encoded in scripts, styles and URLs.
+fn needs_encoding(context: Context, c: char) -> bool {
+    match context {
+        Context::Script | Context::Style => !c.is_ascii_alphanumeric(),
+        Context::UrlPart => !c.is_ascii_alphanumeric() && !"-._~".contains(c),
+        _ => false,
+    }
+}
+
+fn encode(f: &mut dyn Write, context: Context, c: char) -> Result {
+    match context {
+        Context::Script if (c as u32) < 0x100 => write!(f, "\\x{:02X}", c as u32),
+        Context::Script => {
+            let mut units = [0; 2];
+
+            for unit in c.encode_utf16(&mut units) {
+                write!(f, "\\u{:04X}", unit)?;
+            }
+
+            Ok(())
+        }
+        // The space ends the escape sequence
+        Context::Style => write!(f, "\\{:X} ", c as u32),
+        _ => {
+            let mut bytes = [0; 4];
+
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                write!(f, "%{:02X}", byte)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Whether the URL is relative or has one of the allowed schemes.
+fn is_safe_url(url: &str) -> bool {
+    // Browsers decode the character references of attributes before using the URL
+    let url = decode_references(url);
+
+    // Browsers ignore the leading spaces and the tabs and newlines
+    let chars = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'));
+    let mut scheme = String::new();
+
+    for c in chars {
+        match c {
+            ':' => return URL_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()),
+            '/' | '?' | '#' => return true,
+            _ => scheme.push(c),
+        }
+    }
+
+    true
+}
+
+/// Decodes the numeric character references, with or without their `;`, and
+/// the named ones which matter for the scheme of a URL.
+pub(crate) fn decode_references(value: &str) -> Cow<'_, str> {
+    if !value.contains('&') {
+        return Cow::Borrowed(value);
+    }
+
+    let mut out = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        match reference(rest) {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => out.push('&'),
+        }
+    }
+
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+// Character of the reference at the start of `rest`, which follows a `&`,
+// along with the length of the reference
+fn reference(rest: &str) -> Option<(char, usize)> {
+    let number = match rest.strip_prefix('#') {
+        Some(number) => number,
+        None => {
+            return NAMED_REFERENCES
+                .iter()
+                .find(|(name, _)| rest.starts_with(name) && rest[name.len()..].starts_with(';'))
+                .map(|(name, c)| (*c, name.len() + 1));
+        }
+    };
+    let (digits, radix, prefix) = match number.strip_prefix(|c| c == 'x' || c == 'X') {
+        Some(hex) => (hex, 16, 2),
+        None => (number, 10, 1),
+    };
+    let len = digits.find(|c: char| !c.is_digit(radix)).unwrap_or(digits.len());
+
+    if len == 0 {
+        return None;
+    }
+
+    // Invalid code points are replaced like browsers do
+    let c = u32::from_str_radix(&digits[..len], radix)
+        .ok()
+        .filter(|code| *code != 0)
+        .and_then(char::from_u32)
+        .unwrap_or('\u{FFFD}');
+    let semicolon = if digits[len..].starts_with(';') { 1 } else { 0 };
+
+    Some((c, prefix + len + semicolon))
+}
+
+/// Writes the value escaped for the given context.
+#[doc(hidden)]
+pub fn write(f: &mut dyn Write, context: Context, value: &dyn Display) -> Result {
+    write_escaped(f, context, value, false)
+}
+
+fn write_escaped(f: &mut dyn Write, context: Context, value: &dyn Display, strict: bool) -> Result {
+    if context == Context::Url {
+        // The whole value is needed to check the scheme
+        let url = value.to_string();
+        let url = if is_safe_url(&url) { url.as_str() } else { BLOCKED_URL };
+
+        return EscapeWriter { inner: f, context, strict }.write_str(url);
+    }
+
+    write!(EscapeWriter { inner: f, context, strict }, "{}", value)
+}
+
+/// Writes the value as the content of an element, escaping it on the fly.
+#[doc(hidden)]
+pub fn text(f: &mut dyn Write, value: &dyn Display) -> Result {
+    write(f, Context::Text, value)
+}
+
+/// Writes the value inside a double quoted attribute, escaping it on the fly.
+#[doc(hidden)]
+pub fn attribute(f: &mut dyn Write, value: &dyn Display) -> Result {
+    write(f, Context::Attribute, value)
+}
+
+/// Escaping of the values written by the views, chosen with the `escaper`
+/// option of the `views!` macro.
+///
+/// The static parts of the templates are written as they are, only the
+/// values of the `{{ }}` tags and the variable attributes go through the
+/// escaper along with the [`Context`] they are written into.
+///
+/// ```
+/// use reign_view::{Context, Escaper, XmlEscaper};
+///
+/// let mut out = String::new();
+///
+/// XmlEscaper.escape(&mut out, Context::Text, &"Tom & Jerry").unwrap();
+/// assert_eq!(out, "Tom &amp; Jerry");
+/// ```
+pub trait Escaper {
+    /// Writes the value escaped for the given context.
+    fn escape(&self, f: &mut dyn Write, context: Context, value: &dyn Display) -> Result;
+}
+
+/// Escapes values for the HTML context they are written into, used by default.
+///
+/// The content of elements is HTML encoded, attributes have their quotes and
+/// ampersands encoded, URLs with a scheme other than `http`, `https`, `mailto` and
+/// `tel` are blocked, the rest of the URLs is percent-encoded and scripts
+/// and styles have everything except alphanumeric characters escaped.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HtmlEscaper;
+
+impl Escaper for HtmlEscaper {
+    fn escape(&self, f: &mut dyn Write, context: Context, value: &dyn Display) -> Result {
+        write(f, context, value)
+    }
+}
+
+/// Same as [`HtmlEscaper`] but encodes every ASCII character except the
+/// alphanumeric ones as `&#xHH;` in the content of elements and in
+/// attributes, which keeps the values safe even in unquoted attributes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AttributeEscaper;
+
+impl Escaper for AttributeEscaper {
+    fn escape(&self, f: &mut dyn Write, context: Context, value: &dyn Display) -> Result {
+        write_escaped(f, context, value, true)
+    }
+}
+
+/// Escapes the five predefined XML entities in every context, for XML
+/// documents like feeds or sitemaps.
+///
+/// Characters which are not allowed in XML 1.0, like most control
+/// characters, are replaced with `U+FFFD`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct XmlEscaper;
+
+impl Escaper for XmlEscaper {
+    fn escape(&self, f: &mut dyn Write, _: Context, value: &dyn Display) -> Result {
+        write!(XmlWriter { inner: f }, "{}", value)
+    }
+}
+
+struct XmlWriter<'a> {
+    inner: &'a mut dyn Write,
+}
+
+impl Write for XmlWriter<'_> {
+    fn write_str(&mut self, s: &str) -> Result {
+        let mut start = 0;
+
+        for (i, c) in s.char_indices() {
+            let replacement = match c {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '\'' => "&apos;",
+                '"' => "&quot;",
+                '\t' | '\n' | '\r' => continue,
+                '\u{0}'..='\u{1F}' | '\u{FFFE}' | '\u{FFFF}' => "\u{FFFD}",
+                _ => continue,
+            };
+
+            self.inner.write_str(&s[start..i])?;
+            self.inner.write_str(replacement)?;
+            start = i + c.len_utf8();
+        }
+
+        self.inner.write_str(&s[start..])
+    }
+}
+
+/// Writes the values as they are, for plain text documents like the text
+/// part of emails.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoEscaper;
+
+impl Escaper for NoEscaper {
+    fn escape(&self, f: &mut dyn Write, _: Context, value: &dyn Display) -> Result {
+        write!(f, "{}", value)
+    }
+}
+
+/// How a value is written into a template by the `{{ }}` tags and the
+/// variable attributes.
+///
+/// Values which only implement [`std::fmt::Display`] are escaped by the
+/// [`Escaper`] of the view for the [`Context`] they are written into, while
+/// the implementations of this trait decide for themselves. [`Safe`] values and the views generated by `views!`
+/// are written without escaping in the content of elements, so that rendering
+/// a view inside of another one doesn't escape it twice.
+pub trait Escape {
+    /// Writes the value into the given context, using the escaper of the
+    /// view for the parts which need escaping.
+    fn escape(&self, f: &mut dyn Write, context: Context, escaper: &dyn Escaper) -> Result;
+}
+
+impl<T: Escape + ?Sized> Escape for &T {
+    fn escape(&self, f: &mut dyn Write, context: Context, escaper: &dyn Escaper) -> Result {
+        (**self).escape(f, context, escaper)
+    }
+}
+
+impl<T: Escape + ?Sized> Escape for Box<T> {
+    fn escape(&self, f: &mut dyn Write, context: Context, escaper: &dyn Escaper) -> Result {
+        (**self).escape(f, context, escaper)
+    }
+}
+
+/// Trusted markup written as it is, without escaping.
+///
+/// Only wrap values which can't contain markup from untrusted sources,
+/// like the output of a sanitizer. Outside of the content of elements,
+/// like in attributes or scripts, the value is escaped like any other
+/// since being safe markup doesn't make it a safe script.
+///
+/// ```
+/// use reign_view::Safe;
+///
+/// let html = Safe("<b>Hello</b>");
+/// # assert_eq!(html.to_string(), "<b>Hello</b>");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Safe<T>(pub T);
+
+/// Trusted markup built at runtime.
+pub type SafeHtml = Safe<String>;
+
+impl<T: Display> Escape for Safe<T> {
+    fn escape(&self, f: &mut dyn Write, context: Context, escaper: &dyn Escaper) -> Result {
+        match context {
+            Context::Text => write!(f, "{}", self.0),
+            _ => escaper.escape(f, context, &self.0),
+        }
+    }
+}
+
+impl<T: Display> Display for Safe<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result {
+        self.0.fmt(f)
+    }
+}
+
+// The generated views call `(&&Wrap(&value)).escape(f, context, escaper)` which resolves
+// to `ViaEscape` when the value implements `Escape` and to `ViaDisplay` otherwise.
+
+#[doc(hidden)]
+pub struct Wrap<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ViaEscape {
+    fn escape(&self, f: &mut dyn Write, context: Context, escaper: &dyn Escaper) -> Result;
+}
+
+impl<T: Escape + ?Sized> ViaEscape for &Wrap<'_, T> {
+    fn escape(&self, f: &mut dyn Write, context: Context, escaper: &dyn Escaper) -> Result {
+        self.0.escape(f, context, escaper)
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaDisplay {
+    fn escape(&self, f: &mut dyn Write, context: Context, escaper: &dyn Escaper) -> Result;
+}
+
+impl<T: Display + ?Sized> ViaDisplay for Wrap<'_, T> {
+    fn escape(&self, f: &mut dyn Write, context: Context, escaper: &dyn Escaper) -> Result {
+        escaper.escape(f, context, &self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        let mut out = String::new();
+
+        text(&mut out, &"<a href='x'>Tom & \"Jerry\"</a>").unwrap();
+        assert_eq!(
+            out,
+            "&lt;a href=&#x27;x&#x27;&gt;Tom &amp; &quot;Jerry&quot;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_attribute() {
+        let mut out = String::new();
+
+        attribute(&mut out, &"say \"hi\" & <bye>").unwrap();
+        assert_eq!(out, "say &#x22;hi&#x22; &amp; <bye>");
+    }
+
+    #[test]
+    fn test_display() {
+        let mut out = String::new();
+
+        text(&mut out, &42).unwrap();
+        assert_eq!(out, "42");
+    }
+
+    #[test]
+    fn test_url() {
+        let url = |value: &str, context| {
+            let mut out = String::new();
+
+            write(&mut out, context, &value).unwrap();
+            out
+        };
+
+        assert_eq!(url("https://a.com/?q=\"x\"", Context::Url), "https://a.com/?q=&#x22;x&#x22;");
+        assert_eq!(url("/users/1", Context::Url), "/users/1");
+        assert_eq!(url("page:2", Context::Url), BLOCKED_URL);
+        assert_eq!(url(" Java\tScript:alert(1)", Context::Url), BLOCKED_URL);
+        assert_eq!(url("javascript&#58;alert(1)", Context::Url), BLOCKED_URL);
+        assert_eq!(url("javascript&colon;alert(1)", Context::Url), BLOCKED_URL);
+        assert_eq!(url("&#106;avascript:alert(1)", Context::Url), BLOCKED_URL);
+        assert_eq!(url("java&#x73script&#x3A;alert(1)", Context::Url), BLOCKED_URL);
+        assert_eq!(url("/a?b=1&c=2&#x23;", Context::Url), "/a?b=1&amp;c=2&amp;#x23;");
+        assert_eq!(url("a b&c/é", Context::UrlPart), "a%20b%26c%2F%C3%A9");
+    }
+
+    #[test]
+    fn test_script_and_style() {
+        let mut out = String::new();
+
+        write(&mut out, Context::Script, &"</script>'é😀").unwrap();
+        assert_eq!(out, "\\x3C\\x2Fscript\\x3E\\x27\\xE9\\uD83D\\uDE00");
+
+        out.clear();
+        write(&mut out, Context::Style, &"red;}").unwrap();
+        assert_eq!(out, "red\\3B \\7D ");
+    }
+
+    #[test]
+    fn test_attribute_context() {
+        assert_eq!(attribute_context("HREF"), Context::Url);
+        assert_eq!(attribute_context("onclick"), Context::Script);
+        assert_eq!(attribute_context("style"), Context::Style);
+        assert_eq!(attribute_context("title"), Context::Attribute);
+    }
+
+    #[test]
+    fn test_attribute_name() {
+        assert!(is_attribute_name("onclick"));
+        assert!(is_attribute_name("data-x:y.z"));
+        assert!(!is_attribute_name(""));
+        assert!(!is_attribute_name("a b"));
+        assert!(!is_attribute_name("x\"onmouseover=\"alert(1)"));
+        assert!(!is_attribute_name("x><script"));
+        assert!(!is_attribute_name("a/b"));
+    }
+
+    // The extra references are what picks `ViaEscape` over `ViaDisplay`
+    #[allow(clippy::needless_borrow)]
+    #[test]
+    fn test_safe() {
+        let mut out = String::new();
+
+        (&&Wrap(&Safe("<b>&</b>"))).escape(&mut out, Context::Text, &HtmlEscaper).unwrap();
+        (&&Wrap(&"<b>&</b>")).escape(&mut out, Context::Text, &HtmlEscaper).unwrap();
+        (&&Wrap(&Safe("\""))).escape(&mut out, Context::Attribute, &HtmlEscaper).unwrap();
+        (&&Wrap(&Safe("'"))).escape(&mut out, Context::Script, &HtmlEscaper).unwrap();
+
+        assert_eq!(out, "<b>&</b>&lt;b&gt;&amp;&lt;/b&gt;&#x22;\\x27");
+    }
+
+    #[test]
+    fn test_escapers() {
+        let escape = |escaper: &dyn Escaper, context, value: &str| {
+            let mut out = String::new();
+
+            escaper.escape(&mut out, context, &value).unwrap();
+            out
+        };
+
+        assert_eq!(escape(&AttributeEscaper, Context::Attribute, "a b=\"é\""), "a&#x20;b&#x3D;&#x22;é&#x22;");
+        assert_eq!(escape(&AttributeEscaper, Context::Url, "javascript:x"), "about&#x3A;invalid&#x23;blocked");
+        assert_eq!(escape(&AttributeEscaper, Context::Script, "'"), "\\x27");
+        assert_eq!(escape(&XmlEscaper, Context::Text, "<a b='c'>&\"\u{1}\n"), "&lt;a b=&apos;c&apos;&gt;&amp;&quot;\u{FFFD}\n");
+        assert_eq!(escape(&XmlEscaper, Context::Script, "'"), "&apos;");
+        assert_eq!(escape(&NoEscaper, Context::Url, "javascript:<x>"), "javascript:<x>");
+    }
+}
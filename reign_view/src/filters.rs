@@ -0,0 +1,225 @@
+//! Filters applied to the values of interpolations with `{{ value | filter(args) }}`.
+//!
+//! Each filter in the pipeline is a method called on the filter registry of the
+//! views, with the value as the first argument and the arguments of the filter
+//! after it. The registry is [`StdFilters`] unless another one is given to
+//! `views!` with the `filters` option.
+
+use std::{
+    fmt::Display,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Registry of the filters which can be used in the templates.
+///
+/// Every method has a default implementation from the standard library of
+/// filters. A registry can override them, and adds filters of its own as
+/// inherent methods taking the value as a reference.
+///
+/// ```
+/// use reign_view::Filters;
+/// use std::fmt::Display;
+///
+/// pub struct AppFilters;
+///
+/// impl Filters for AppFilters {}
+///
+/// impl AppFilters {
+///     // Used as `{{ title | shout }}`
+///     pub fn shout<T: Display + ?Sized>(&self, value: &T) -> String {
+///         format!("{}!", self.upper(value))
+///     }
+/// }
+///
+/// assert_eq!(AppFilters.shout("hi"), "HI!");
+/// ```
+pub trait Filters {
+    /// Converts the value to uppercase.
+    fn upper<T: Display + ?Sized>(&self, value: &T) -> String {
+        upper(value)
+    }
+
+    /// Converts the value to lowercase.
+    fn lower<T: Display + ?Sized>(&self, value: &T) -> String {
+        lower(value)
+    }
+
+    /// Converts the first character of the value to uppercase and the rest to lowercase.
+    fn capitalize<T: Display + ?Sized>(&self, value: &T) -> String {
+        capitalize(value)
+    }
+
+    /// Removes the whitespace around the value.
+    fn trim<T: Display + ?Sized>(&self, value: &T) -> String {
+        trim(value)
+    }
+
+    /// Keeps the first `len` characters of the value, followed by `...` if it was longer.
+    fn truncate<T: Display + ?Sized>(&self, value: &T, len: usize) -> String {
+        truncate(value, len)
+    }
+
+    /// Joins the items with the separator.
+    fn join<T: Display>(&self, items: &[T], separator: &str) -> String {
+        join(items, separator)
+    }
+
+    /// Chooses the singular form when the count is one and the plural form otherwise.
+    fn pluralize<'s, T: PartialEq + From<u8>>(&self, count: &T, singular: &'s str, plural: &'s str) -> &'s str {
+        pluralize(count, singular, plural)
+    }
+
+    /// Formats the time in UTC, see [`date`] for the format.
+    fn date(&self, time: &SystemTime, format: &str) -> String {
+        date(time, format)
+    }
+}
+
+/// The standard library of filters, used by default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdFilters;
+
+impl Filters for StdFilters {}
+
+pub fn upper<T: Display + ?Sized>(value: &T) -> String {
+    value.to_string().to_uppercase()
+}
+
+pub fn lower<T: Display + ?Sized>(value: &T) -> String {
+    value.to_string().to_lowercase()
+}
+
+pub fn capitalize<T: Display + ?Sized>(value: &T) -> String {
+    let value = value.to_string();
+    let mut chars = value.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.as_str().to_lowercase().chars()).collect(),
+        None => value,
+    }
+}
+
+pub fn trim<T: Display + ?Sized>(value: &T) -> String {
+    value.to_string().trim().to_string()
+}
+
+pub fn truncate<T: Display + ?Sized>(value: &T, len: usize) -> String {
+    let value = value.to_string();
+
+    match value.char_indices().nth(len) {
+        Some((end, _)) => format!("{}...", &value[..end]),
+        None => value,
+    }
+}
+
+pub fn join<T: Display>(items: &[T], separator: &str) -> String {
+    let mut out = String::new();
+
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            out.push_str(separator);
+        }
+
+        out.push_str(&item.to_string());
+    }
+
+    out
+}
+
+pub fn pluralize<'s, T: PartialEq + From<u8>>(count: &T, singular: &'s str, plural: &'s str) -> &'s str {
+    if *count == T::from(1) {
+        singular
+    } else {
+        plural
+    }
+}
+
+/// Formats the time in UTC with `%Y` for the year, `%m` for the month, `%d`
+/// for the day, `%H` for the hours, `%M` for the minutes, `%S` for the seconds
+/// and `%%` for a `%`. The numbers other than the year have two digits.
+pub fn date(time: &SystemTime, format: &str) -> String {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(err) => -(err.duration().as_secs() as i64),
+    };
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+    let secs = secs.rem_euclid(86400);
+
+    let mut out = String::new();
+    let mut chars = format.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('Y') => out.push_str(&year.to_string()),
+            Some('m') => out.push_str(&format!("{:02}", month)),
+            Some('d') => out.push_str(&format!("{:02}", day)),
+            Some('H') => out.push_str(&format!("{:02}", secs / 3600)),
+            Some('M') => out.push_str(&format!("{:02}", secs / 60 % 60)),
+            Some('S') => out.push_str(&format!("{:02}", secs % 60)),
+            Some('%') => out.push('%'),
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+            }
+            None => out.push('%'),
+        }
+    }
+
+    out
+}
+
+// Year, month and day of the days since the Unix epoch in the proleptic
+// Gregorian calendar, from http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+
+    (yoe + era * 400 + (month <= 2) as i64, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_text_filters() {
+        assert_eq!(StdFilters.upper("abc"), "ABC");
+        assert_eq!(StdFilters.lower(&'A'), "a");
+        assert_eq!(StdFilters.capitalize("hELLO world"), "Hello world");
+        assert_eq!(StdFilters.capitalize(""), "");
+        assert_eq!(StdFilters.trim(" a b "), "a b");
+        assert_eq!(StdFilters.truncate("héllo world", 5), "héllo...");
+        assert_eq!(StdFilters.truncate("héllo", 5), "héllo");
+    }
+
+    #[test]
+    fn test_join_and_pluralize() {
+        assert_eq!(StdFilters.join(&["a", "b", "c"], ", "), "a, b, c");
+        assert_eq!(StdFilters.join(&Vec::<u8>::new(), ", "), "");
+        assert_eq!(StdFilters.pluralize(&1usize, "item", "items"), "item");
+        assert_eq!(StdFilters.pluralize(&0u32, "item", "items"), "items");
+        assert_eq!(StdFilters.pluralize(&1.5f64, "item", "items"), "items");
+    }
+
+    #[test]
+    fn test_date() {
+        let time = UNIX_EPOCH + Duration::from_secs(951_782_400 + 3723);
+
+        assert_eq!(StdFilters.date(&time, "%Y-%m-%d %H:%M:%S %%"), "2000-02-29 01:02:03 %");
+        assert_eq!(date(&UNIX_EPOCH, "%d/%m/%Y"), "01/01/1970");
+        assert_eq!(date(&(UNIX_EPOCH - Duration::from_secs(1)), "%Y-%m-%d %H:%M:%S"), "1969-12-31 23:59:59");
+        assert_eq!(date(&UNIX_EPOCH, "%q%"), "%q%");
+    }
+}
@@ -0,0 +1,964 @@
+//! Hot reload mode of the views, enabled with the `hot_reload` option of
+//! the `views!` macro.
+//!
+//! In debug builds the generated views read their template from disk on
+//! every render and interpret it against their fields, so that changes to
+//! the templates show up without recompiling. Release builds always use
+//! the compiled templates.
+//!
+//! Only a subset of the templates can be interpreted. Text, attributes,
+//! slots and the `!if`, `!else-if`, `!else` and `!for` control attributes
+//! are supported along with simple expressions on the fields, like
+//! comparisons or `len()`. When the template uses anything else, for
+//! example a component, the view falls back to the compiled template and
+//! logs a warning once through the `log` crate.
+
+use crate::{
+    binding::{ClassList, StyleList},
+    escape::{attribute_context, Context, Escaper},
+    Slots,
+};
+use log::warn;
+use once_cell::sync::Lazy;
+use reign_view_ast::{
+    decode_references,
+    expr::Expr,
+    is_reserved_tag,
+    minify::minify,
+    parse::{consts::VOID_TAGS, parse},
+    pat::{For, Pat},
+    Attribute, Code, Element, ItemTemplate, Node, StringPart,
+};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::{self, Write},
+    fs::{metadata, read_to_string},
+    path::PathBuf,
+    rc::Rc,
+    slice::from_ref,
+    sync::{Mutex, RwLock},
+    time::SystemTime,
+};
+use syn::{BinOp, Lit, UnOp};
+
+/// Value of a field as seen by the interpreter.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+    Str(Cow<'a, str>),
+    Int(i128),
+    Float(f64),
+    Bool(bool),
+    List(Vec<Value<'a>>),
+    None,
+    /// Value of a type which can not be used by the interpreter
+    Opaque,
+}
+
+/// Types of fields which can be used by the interpreter.
+///
+/// Implement it for your own types to use them in hot reloaded templates.
+pub trait ToValue {
+    fn to_value(&self) -> Value<'_>;
+}
+
+impl ToValue for str {
+    fn to_value(&self) -> Value<'_> {
+        Value::Str(Cow::Borrowed(self))
+    }
+}
+
+impl ToValue for String {
+    fn to_value(&self) -> Value<'_> {
+        Value::Str(Cow::Borrowed(self))
+    }
+}
+
+impl ToValue for char {
+    fn to_value(&self) -> Value<'_> {
+        Value::Str(Cow::Owned(self.to_string()))
+    }
+}
+
+impl ToValue for bool {
+    fn to_value(&self) -> Value<'_> {
+        Value::Bool(*self)
+    }
+}
+
+macro_rules! impl_to_value {
+    ($variant:ident, $as:ty, $($ty:ty),*) => {
+        $(
+            impl ToValue for $ty {
+                fn to_value(&self) -> Value<'_> {
+                    Value::$variant(*self as $as)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_value!(Int, i128, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+impl_to_value!(Float, f64, f32, f64);
+
+impl<T: ToValue> ToValue for Option<T> {
+    fn to_value(&self) -> Value<'_> {
+        match self {
+            Some(value) => value.to_value(),
+            None => Value::None,
+        }
+    }
+}
+
+impl<T: ToValue> ToValue for [T] {
+    fn to_value(&self) -> Value<'_> {
+        Value::List(self.iter().map(ToValue::to_value).collect())
+    }
+}
+
+impl<T: ToValue, const N: usize> ToValue for [T; N] {
+    fn to_value(&self) -> Value<'_> {
+        self[..].to_value()
+    }
+}
+
+impl<T: ToValue> ToValue for Vec<T> {
+    fn to_value(&self) -> Value<'_> {
+        self[..].to_value()
+    }
+}
+
+impl<T: ToValue + ?Sized> ToValue for &T {
+    fn to_value(&self) -> Value<'_> {
+        (**self).to_value()
+    }
+}
+
+// The generated views call `(&&Wrap(&self.field)).value()` which resolves to
+// `ViaValue` when the field implements `ToValue` and to `ViaOpaque` otherwise.
+
+#[doc(hidden)]
+pub struct Wrap<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ViaValue<'a> {
+    fn value(&self) -> Value<'a>;
+}
+
+impl<'a, T: ToValue + ?Sized> ViaValue<'a> for &Wrap<'a, T> {
+    fn value(&self) -> Value<'a> {
+        self.0.to_value()
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaOpaque<'a> {
+    fn value(&self) -> Value<'a>;
+}
+
+impl<'a, T: ?Sized> ViaOpaque<'a> for Wrap<'a, T> {
+    fn value(&self) -> Value<'a> {
+        Value::Opaque
+    }
+}
+
+enum Error {
+    Unsupported(String),
+    Fmt,
+}
+
+impl From<fmt::Error> for Error {
+    fn from(_: fmt::Error) -> Self {
+        Error::Fmt
+    }
+}
+
+fn unsupported<T>(message: &str) -> Result<T, Error> {
+    Err(Error::Unsupported(message.to_string()))
+}
+
+static WARNED: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Template parsed from a file, along with the modification time and the
+/// length of the file when it was read.
+struct Cached {
+    modified: SystemTime,
+    len: u64,
+    template: Rc<ItemTemplate>,
+}
+
+// Keyed on the path of the file and whether the template is minified. The
+// templates hold `syn` values which can not be shared between threads.
+thread_local! {
+    static TEMPLATES: RefCell<HashMap<(String, bool), Cached>> = RefCell::new(HashMap::new());
+}
+
+static BASE_DIR: Lazy<RwLock<Option<PathBuf>>> = Lazy::new(|| RwLock::new(None));
+
+/// Reads the templates from the given directory instead of the directory of
+/// the crate they were compiled in, for example when the sources are mounted
+/// somewhere else than where the application was built.
+///
+/// The paths of the templates inside the directory stay the same, so
+/// `src/views/pages/home.html` is read from `<dir>/src/views/pages/home.html`.
+///
+/// ```
+/// reign_view::hot::set_base_dir("/app");
+/// ```
+pub fn set_base_dir<P: Into<PathBuf>>(dir: P) {
+    if let Ok(mut base_dir) = BASE_DIR.write() {
+        *base_dir = Some(dir.into());
+    }
+}
+
+/// Template of a view as compiled by the `views!` macro.
+#[doc(hidden)]
+pub struct Template<'a> {
+    /// Directory of the crate the view was compiled in
+    pub dir: &'a str,
+    /// Path of the template file relative to `dir`
+    pub path: &'a str,
+    /// Path of the module of the file inside the views, like `shared`
+    pub module: &'a str,
+    /// Name of the main template in the file
+    pub root: &'a str,
+    /// Name of the template of the view
+    pub name: &'a str,
+    pub minify: bool,
+}
+
+/// Renders the template of the view from its file, returning `None` if
+/// the compiled template should be used instead.
+#[doc(hidden)]
+pub fn render(
+    f: &mut dyn Write,
+    view: &Template,
+    fields: &[(&str, Value)],
+    slots: &Slots,
+    escaper: &dyn Escaper,
+) -> Option<fmt::Result> {
+    let base_dir = BASE_DIR.read().ok().and_then(|dir| dir.clone());
+    let path = base_dir.unwrap_or_else(|| PathBuf::from(view.dir)).join(view.path);
+    let path = path.to_string_lossy();
+    let name = view.name;
+
+    let mut out = String::new();
+    let result = read_template(&path, view.module, view.root, view.minify).and_then(|file| {
+        let template = find_template(&file, name)?;
+
+        Interpreter {
+            fields,
+            slots,
+            scopes: vec![],
+            scope: template.scope.clone(),
+            escaper,
+        }
+        .nodes(&template.children, &mut out)
+    });
+
+    match result {
+        Ok(()) => Some(f.write_str(&out)),
+        Err(Error::Fmt) => Some(Err(fmt::Error)),
+        Err(Error::Unsupported(message)) => {
+            let warning = format!("{}: {}", path, message);
+
+            if WARNED.lock().map_or(false, |mut warned| warned.insert(warning.clone())) {
+                warn!("hot reload of `{}` falls back to the compiled template, {}", name, warning);
+            }
+
+            None
+        }
+    }
+}
+
+/// Parses the file again only when it was modified since the last render.
+fn read_template(path: &str, module: &str, root: &str, minified: bool) -> Result<Rc<ItemTemplate>, Error> {
+    let key = (path.to_string(), minified);
+    let stamp = metadata(path).and_then(|meta| Ok((meta.modified()?, meta.len()))).ok();
+
+    let cached = TEMPLATES.with(|templates| match (templates.borrow().get(&key), stamp) {
+        (Some(cached), Some(stamp)) if (cached.modified, cached.len) == stamp => Some(cached.template.clone()),
+        _ => None,
+    });
+
+    if let Some(template) = cached {
+        return Ok(template);
+    }
+
+    let data = read_to_string(path)
+        .map_err(|err| Error::Unsupported(format!("unable to read the template: {}", err)))?
+        .replace("\r\n", "\n");
+    let mut template = parse(data, root.to_string(), module)
+        .map_err(|err| Error::Unsupported(format!("unable to parse the template: {}", err)))?;
+
+    if minified {
+        minify(&mut template);
+    }
+
+    let template = Rc::new(template);
+
+    if let Some((modified, len)) = stamp {
+        let cached = Cached {
+            modified,
+            len,
+            template: template.clone(),
+        };
+
+        TEMPLATES.with(|templates| templates.borrow_mut().insert(key, cached));
+    }
+
+    Ok(template)
+}
+
+fn find_template<'t>(template: &'t ItemTemplate, name: &str) -> Result<&'t ItemTemplate, Error> {
+    if template.name == name {
+        return Ok(template);
+    }
+
+    match template.named.iter().find(|named| named.name == name) {
+        Some(named) => Ok(named),
+        None => unsupported("the template was removed"),
+    }
+}
+
+struct Interpreter<'a, 'b> {
+    fields: &'b [(&'b str, Value<'a>)],
+    slots: &'b Slots<'b>,
+    scopes: Vec<(String, Value<'a>)>,
+    scope: Option<String>,
+    escaper: &'b dyn Escaper,
+}
+
+impl<'a, 'b> Interpreter<'a, 'b> {
+    fn nodes(&mut self, nodes: &[Node], out: &mut String) -> Result<(), Error> {
+        let mut i = 0;
+
+        while i < nodes.len() {
+            if let Node::Element(e) = &nodes[i] {
+                if e.template_name().is_some() {
+                    i += 1;
+                    continue;
+                }
+
+                if let Some(attr) = e.control_attr("if") {
+                    // Anything other than elements in between the `!if`, `!else-if`
+                    // and `!else` elements is left out, like the compiled template
+                    let mut matched = self.bool(&attr.value)?;
+                    let mut last = i;
+
+                    if matched {
+                        self.element(e, out)?;
+                    }
+
+                    for (j, sibling) in nodes.iter().enumerate().skip(i + 1) {
+                        let sibling = match sibling {
+                            Node::Element(s) if s.template_name().is_none() => s,
+                            _ => continue,
+                        };
+
+                        if sibling.control_attr("else").is_some() {
+                            if !matched {
+                                self.element(sibling, out)?;
+                            }
+
+                            last = j;
+                            break;
+                        } else if let Some(attr) = sibling.control_attr("else-if") {
+                            if !matched && self.bool(&attr.value)? {
+                                matched = true;
+                                self.element(sibling, out)?;
+                            }
+
+                            last = j;
+                        } else {
+                            break;
+                        }
+                    }
+
+                    i = last + 1;
+                    continue;
+                }
+
+                if e.control_attr("else").is_some() || e.control_attr("else-if").is_some() {
+                    return unsupported("expected `!if` element before `!else` or `!else-if`");
+                }
+            }
+
+            self.node(&nodes[i], out)?;
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    fn node(&mut self, node: &Node, out: &mut String) -> Result<(), Error> {
+        match node {
+            Node::Element(e) => self.element(e, out),
+            Node::Text(t) => self.parts(&t.content, out, false, Context::Text),
+            Node::Comment(c) => {
+                write!(out, "<!--{}-->", c.content)?;
+                Ok(())
+            }
+            Node::Doctype(d) => {
+                out.push_str(&d.content);
+                Ok(())
+            }
+        }
+    }
+
+    fn element(&mut self, e: &Element, out: &mut String) -> Result<(), Error> {
+        if e.control_attr("match").is_some() || e.control_attr("case").is_some() {
+            return unsupported("`!match` is not supported");
+        }
+
+        if e.control_attr("empty").is_some() {
+            return unsupported("`!empty` is not supported");
+        }
+
+        let attr = match e.control_attr("for") {
+            Some(attr) => attr,
+            None => return self.element_content(e, out),
+        };
+
+        let for_ = match &attr.value {
+            Code::For(for_) => for_,
+            _ => return unsupported("expected a `!for` loop"),
+        };
+
+        let name = loop_variable(for_)?;
+
+        let items = match self.expr(&for_.expr)? {
+            Value::List(items) => items,
+            _ => return unsupported("`!for` can only loop over a list"),
+        };
+
+        for item in items {
+            self.scopes.push((name.clone(), item));
+
+            let result = self.element_content(e, out);
+
+            self.scopes.pop();
+            result?;
+        }
+
+        Ok(())
+    }
+
+    fn element_content(&mut self, e: &Element, out: &mut String) -> Result<(), Error> {
+        if e.name == "template" {
+            return self.nodes(&e.children, out);
+        }
+
+        if e.name == "slot" {
+            return match self.slots.get(&e.slot_name()) {
+                Some(slot) => Ok(slot(out)?),
+                None => self.nodes(&e.children, out),
+            };
+        }
+
+        if e.name.contains(':') || !is_reserved_tag(&e.name) {
+            return unsupported(&format!("components like `<{}>` are not supported", e.name));
+        }
+
+        if e.inherits_attrs() {
+            return unsupported("forwarding attributes with `!inherit-attrs` is not supported");
+        }
+
+        write!(out, "<{}", e.name)?;
+
+        if let Some(scope) = &self.scope {
+            write!(out, " {}", scope)?;
+        }
+
+        let mut merged = vec![];
+
+        for attr in &e.attrs {
+            let name = match attr {
+                Attribute::Normal(n) => n.name.as_str(),
+                Attribute::Variable(v) => v.name.as_str(),
+                _ => "",
+            };
+
+            if name == ".." {
+                return unsupported("spreading attributes with `:..` is not supported");
+            }
+
+            // Same merging of `class` and `style` as the compiled views
+            if (name == "class" || name == "style") && has_variable_attr(e, name) {
+                if !merged.contains(&name) {
+                    merged.push(name);
+                    self.binding(e, name, out)?;
+                }
+
+                continue;
+            }
+
+            match attr {
+                Attribute::Normal(n) => {
+                    let context = attribute_context(&n.name);
+
+                    write!(out, " {}=\"", n.name)?;
+
+                    // Same check of the whole URL as the compiled views
+                    if context == Context::Url && n.value.builds_scheme() {
+                        let mut url = String::new();
+
+                        for part in &n.value.parts {
+                            match part {
+                                StringPart::Normal(s) => url.push_str(&decode_references(s)),
+                                StringPart::Expr(_) => self.parts(from_ref(part), &mut url, false, Context::UrlPart)?,
+                            }
+                        }
+
+                        self.escaper.escape(out, context, &url)?;
+                    } else {
+                        self.parts(&n.value.parts, out, true, context)?;
+                    }
+
+                    out.push('"');
+                }
+                Attribute::Variable(v) => match self.code(&v.value)? {
+                    // Same as `AttrValue` for booleans and options
+                    Value::Bool(true) => write!(out, " {}", v.name)?,
+                    Value::Bool(false) | Value::None => {}
+                    value => {
+                        write!(out, " {}=\"", v.name)?;
+                        self.escaper.escape(out, attribute_context(&v.name), &text(&value)?)?;
+                        out.push('"');
+                    }
+                },
+                Attribute::Dynamic(_) => return unsupported("dynamic attributes are not supported"),
+                Attribute::Control(_) => {}
+            }
+        }
+
+        out.push('>');
+        match e.name.as_str() {
+            "script" => self.raw_children(e, out, Context::Script)?,
+            "style" => self.raw_children(e, out, Context::Style)?,
+            _ => self.nodes(&e.children, out)?,
+        }
+
+        if !VOID_TAGS.contains(&e.name.as_str()) {
+            write!(out, "</{}>", e.name)?;
+        }
+
+        Ok(())
+    }
+
+    fn binding(&mut self, e: &Element, name: &str, out: &mut String) -> Result<(), Error> {
+        let mut values = vec![];
+
+        for attr in &e.attrs {
+            match attr {
+                Attribute::Normal(n) if n.name == name => match n.value.value() {
+                    Some(value) => values.push((value, true)),
+                    None if name == "style" => {
+                        let mut value = String::new();
+
+                        self.parts(&n.value.parts, &mut value, false, Context::Style)?;
+                        values.push((value, true));
+                    }
+                    None => return unsupported(&format!("interpolation in `{}` with `:{}` is not supported", name, name)),
+                },
+                Attribute::Variable(v) if v.name == name => match self.code(&v.value)? {
+                    Value::Str(s) => values.push((s.into_owned(), false)),
+                    Value::List(items) => {
+                        for item in items {
+                            match item {
+                                Value::Str(s) => values.push((s.into_owned(), false)),
+                                _ => return unsupported(&format!("the value of `:{}` is not supported", name)),
+                            }
+                        }
+                    }
+                    _ => return unsupported(&format!("the value of `:{}` is not supported", name)),
+                },
+                _ => {}
+            }
+        }
+
+        if name == "class" {
+            let mut list = ClassList::new();
+
+            values.iter().for_each(|(value, _)| list.add(Cow::Borrowed(value)));
+            list.write(out, self.escaper)?;
+        } else {
+            let mut list = StyleList::new();
+
+            for (value, trusted) in &values {
+                if *trusted {
+                    list.add_static(Cow::Borrowed(value));
+                } else {
+                    list.add(Cow::Borrowed(value));
+                }
+            }
+
+            list.write(out, self.escaper)?;
+        }
+
+        Ok(())
+    }
+
+    fn parts(&mut self, parts: &[StringPart], out: &mut String, in_attr: bool, context: Context) -> Result<(), Error> {
+        for (i, part) in parts.iter().enumerate() {
+            match part {
+                StringPart::Normal(s) if in_attr => out.push_str(&s.replace('"', "&#x22;")),
+                StringPart::Normal(s) => out.push_str(s),
+                StringPart::Expr(code) => {
+                    let value = self.code(code)?;
+                    let context = match context {
+                        Context::Url if i > 0 => Context::UrlPart,
+                        context => context,
+                    };
+
+                    self.escaper.escape(out, context, &text(&value)?)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn raw_children(&mut self, e: &Element, out: &mut String, context: Context) -> Result<(), Error> {
+        for child in &e.children {
+            match child {
+                Node::Text(t) => self.parts(&t.content, out, false, context)?,
+                _ => return unsupported(&format!("expected only text inside of `<{}>`", e.name)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn code(&self, code: &Code) -> Result<Value<'a>, Error> {
+        match code {
+            Code::Expr(expr) => self.expr(expr),
+            Code::For(_) => unsupported("unexpected `!for` loop"),
+            Code::Case(_) => unsupported("unexpected `!case` pattern"),
+            Code::Let(_) => unsupported("`let` conditions are not supported"),
+        }
+    }
+
+    fn bool(&self, code: &Code) -> Result<bool, Error> {
+        match self.code(code)? {
+            Value::Bool(b) => Ok(b),
+            _ => unsupported("conditions need to be booleans"),
+        }
+    }
+
+    fn field(&self, name: &str) -> Result<Value<'a>, Error> {
+        let scoped = self.scopes.iter().rev().find(|(scope, _)| scope == name);
+        let field = self.fields.iter().find(|(field, _)| *field == name);
+
+        match scoped.map(|(_, value)| value).or_else(|| field.map(|(_, value)| value)) {
+            Some(Value::Opaque) => unsupported(&format!("the type of `{}` does not implement `ToValue`", name)),
+            Some(value) => Ok(value.clone()),
+            None if name == "None" => Ok(Value::None),
+            None => unsupported(&format!("`{}` is not a field of the view", name)),
+        }
+    }
+
+    fn expr(&self, expr: &Expr) -> Result<Value<'a>, Error> {
+        match expr {
+            Expr::Path(p) => match p.path.get_ident() {
+                Some(ident) => self.field(&ident.to_string()),
+                None => unsupported("paths are not supported"),
+            },
+            Expr::Lit(lit) => literal(lit),
+            Expr::Paren(e) => self.expr(&e.expr),
+            Expr::Group(e) => self.expr(&e.expr),
+            Expr::Reference(e) => self.expr(&e.expr),
+            Expr::Type(e) => self.expr(&e.expr),
+            Expr::Unary(e) => match (&e.op, self.expr(&e.expr)?) {
+                (UnOp::Not(_), Value::Bool(b)) => Ok(Value::Bool(!b)),
+                (UnOp::Neg(_), Value::Int(n)) => Ok(Value::Int(-n)),
+                (UnOp::Neg(_), Value::Float(n)) => Ok(Value::Float(-n)),
+                (UnOp::Deref(_), value) => Ok(value),
+                _ => unsupported("unsupported unary operation"),
+            },
+            Expr::Binary(e) => {
+                let left = self.expr(&e.left)?;
+
+                // Short circuit like Rust does
+                match (&e.op, &left) {
+                    (BinOp::And(_), Value::Bool(false)) => return Ok(Value::Bool(false)),
+                    (BinOp::Or(_), Value::Bool(true)) => return Ok(Value::Bool(true)),
+                    _ => {}
+                }
+
+                binary(&e.op, left, self.expr(&e.right)?)
+            }
+            Expr::MethodCall(e) => {
+                let receiver = self.expr(&e.receiver)?;
+                let args = e
+                    .args
+                    .iter()
+                    .map(|arg| self.expr(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                method(&e.method.to_string(), receiver, args)
+            }
+            Expr::Filter(_) => unsupported("filters are not supported"),
+            Expr::Message(_) => unsupported("translated messages are not supported"),
+            _ => unsupported("unsupported expression"),
+        }
+    }
+}
+
+fn loop_variable(for_: &For) -> Result<String, Error> {
+    match &for_.pat {
+        Pat::Ident(p) => Ok(p.ident.to_string()),
+        _ => unsupported("`!for` only supports a single loop variable"),
+    }
+}
+
+fn literal<'a>(lit: &Lit) -> Result<Value<'a>, Error> {
+    match lit {
+        Lit::Str(s) => Ok(Value::Str(Cow::Owned(s.value()))),
+        Lit::Char(c) => Ok(Value::Str(Cow::Owned(c.value().to_string()))),
+        Lit::Bool(b) => Ok(Value::Bool(b.value)),
+        Lit::Int(n) => n.base10_parse().map(Value::Int).or_else(|_| unsupported("invalid integer")),
+        Lit::Float(n) => n.base10_parse().map(Value::Float).or_else(|_| unsupported("invalid float")),
+        _ => unsupported("unsupported literal"),
+    }
+}
+
+fn text<'a>(value: &'a Value) -> Result<Cow<'a, str>, Error> {
+    match value {
+        Value::Str(s) => Ok(Cow::Borrowed(s)),
+        Value::Int(n) => Ok(Cow::Owned(n.to_string())),
+        Value::Float(n) => Ok(Cow::Owned(n.to_string())),
+        Value::Bool(b) => Ok(Cow::Owned(b.to_string())),
+        _ => unsupported("only strings, numbers and booleans can be displayed"),
+    }
+}
+
+fn binary<'a>(op: &BinOp, left: Value<'a>, right: Value<'a>) -> Result<Value<'a>, Error> {
+    use Value::*;
+
+    let value = match (op, left, right) {
+        (BinOp::And(_), Bool(a), Bool(b)) => Bool(a && b),
+        (BinOp::Or(_), Bool(a), Bool(b)) => Bool(a || b),
+        (BinOp::Eq(_), a, b) => Bool(a == b),
+        (BinOp::Ne(_), a, b) => Bool(a != b),
+        (BinOp::Lt(_), a, b) => Bool(compare(&a, &b)? == std::cmp::Ordering::Less),
+        (BinOp::Le(_), a, b) => Bool(compare(&a, &b)? != std::cmp::Ordering::Greater),
+        (BinOp::Gt(_), a, b) => Bool(compare(&a, &b)? == std::cmp::Ordering::Greater),
+        (BinOp::Ge(_), a, b) => Bool(compare(&a, &b)? != std::cmp::Ordering::Less),
+        // The compiled view knows the actual type when these overflow
+        (BinOp::Add(_), Int(a), Int(b)) => checked(a.checked_add(b))?,
+        (BinOp::Sub(_), Int(a), Int(b)) => checked(a.checked_sub(b))?,
+        (BinOp::Mul(_), Int(a), Int(b)) => checked(a.checked_mul(b))?,
+        (BinOp::Div(_), Int(a), Int(b)) => checked(a.checked_div(b))?,
+        (BinOp::Rem(_), Int(a), Int(b)) => checked(a.checked_rem(b))?,
+        (BinOp::Add(_), Float(a), Float(b)) => Float(a + b),
+        (BinOp::Sub(_), Float(a), Float(b)) => Float(a - b),
+        (BinOp::Mul(_), Float(a), Float(b)) => Float(a * b),
+        (BinOp::Div(_), Float(a), Float(b)) => Float(a / b),
+        _ => return unsupported("unsupported binary operation"),
+    };
+
+    Ok(value)
+}
+
+fn checked<'a>(value: Option<i128>) -> Result<Value<'a>, Error> {
+    match value {
+        Some(value) => Ok(Value::Int(value)),
+        None => unsupported("integer overflow"),
+    }
+}
+
+fn compare(left: &Value, right: &Value) -> Result<std::cmp::Ordering, Error> {
+    let ordering = match (left, right) {
+        (Value::Int(a), Value::Int(b)) => a.partial_cmp(b),
+        (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+        (Value::Str(a), Value::Str(b)) => a.partial_cmp(b),
+        _ => None,
+    };
+
+    match ordering {
+        Some(ordering) => Ok(ordering),
+        None => unsupported("unsupported comparison"),
+    }
+}
+
+fn has_variable_attr(e: &Element, name: &str) -> bool {
+    e.attrs.iter().any(|attr| matches!(attr, Attribute::Variable(v) if v.name == name))
+}
+
+fn method<'a>(name: &str, receiver: Value<'a>, mut args: Vec<Value<'a>>) -> Result<Value<'a>, Error> {
+    let value = match (name, receiver, args.len()) {
+        ("len", Value::Str(s), 0) => Value::Int(s.len() as i128),
+        ("len", Value::List(l), 0) => Value::Int(l.len() as i128),
+        ("is_empty", Value::Str(s), 0) => Value::Bool(s.is_empty()),
+        ("is_empty", Value::List(l), 0) => Value::Bool(l.is_empty()),
+        ("is_some", value, 0) => Value::Bool(value != Value::None),
+        ("is_none", value, 0) => Value::Bool(value == Value::None),
+        ("unwrap_or", Value::None, 1) => args.remove(0),
+        ("unwrap_or", value, 1) => value,
+        ("to_string", value, 0) => Value::Str(Cow::Owned(text(&value)?.into_owned())),
+        _ => return unsupported(&format!("the method `{}` is not supported", name)),
+    };
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HtmlEscaper;
+    use reign_view_ast::parse::consts::URL_ATTRIBUTES;
+
+    fn interpret(template: &str, fields: &[(&str, Value)]) -> Option<String> {
+        let template = parse(template.to_string(), "Test".to_string(), "").ok()?;
+        let slots = Slots::default();
+        let mut out = String::new();
+
+        Interpreter {
+            fields,
+            slots: &slots,
+            scopes: vec![],
+            scope: template.scope.clone(),
+            escaper: &HtmlEscaper,
+        }
+        .nodes(&template.children, &mut out)
+        .ok()?;
+
+        Some(out)
+    }
+
+    #[test]
+    fn test_text_and_attributes() {
+        let title = "Tom & Jerry".to_string();
+
+        assert_eq!(
+            interpret(
+                "<template><p class=\"a\" :title=\"title\">{{ title }} {{ count: u32 }}</p></template>",
+                &[("count", 3u32.to_value()), ("title", title.to_value())],
+            ),
+            Some("<p class=\"a\" title=\"Tom &amp; Jerry\">Tom &amp; Jerry 3</p>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_url_scheme() {
+        assert_eq!(
+            interpret(
+                "<template><a href=\"{{ scheme }}:{{ rest }}\"></a><a href=\"https://a.com/{{ rest }}\"></a></template>",
+                &[("rest", "a b".to_value()), ("scheme", "javascript".to_value())],
+            ),
+            Some("<a href=\"about:invalid#blocked\"></a><a href=\"https://a.com/a%20b\"></a>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_boolean_and_optional_attributes() {
+        assert_eq!(
+            interpret(
+                "<template><input :disabled=\"disabled\" :checked=\"checked\" :title=\"title\"></template>",
+                &[
+                    ("checked", false.to_value()),
+                    ("disabled", true.to_value()),
+                    ("title", None::<&str>.to_value()),
+                ],
+            ),
+            Some("<input disabled>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_class_and_style_bindings() {
+        let classes = vec!["b", "a"];
+
+        assert_eq!(
+            interpret(
+                "<template><p class=\"a\" :class=\"classes\" :style=\"style\"></p></template>",
+                &[("classes", classes.to_value()), ("style", "color: red".to_value())],
+            ),
+            Some("<p class=\"a b\" style=\"color: red\"></p>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_control_attributes() {
+        let items = vec!["a", "b"];
+
+        assert_eq!(
+            interpret(
+                "<template><b !if=\"items.len() > 2\">many</b> <i !else-if=\"!items.is_empty()\">few</i> <u !else>none</u>\
+                 <li !for=\"item in items\">{{ item }}</li></template>",
+                &[("items", items.to_value())],
+            ),
+            Some("<i>few</i><li>a</li><li>b</li>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_scoped_style() {
+        let output = interpret("<template><p><b>a</b></p></template><style scoped>p {}</style>", &[]).unwrap();
+        let scope = output.trim_start_matches("<p ").split('>').next().unwrap();
+
+        assert!(scope.starts_with("data-r-"));
+        assert_eq!(output, format!("<p {0}><b {0}>a</b></p>", scope));
+    }
+
+    #[test]
+    fn test_template_cache() {
+        let path = std::env::temp_dir().join(format!("reign_hot_cache_{}.html", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        std::fs::write(path, "<template>a</template>").unwrap();
+
+        let first = read_template(path, "", "Test", false).ok().unwrap();
+        let second = read_template(path, "", "Test", false).ok().unwrap();
+
+        assert!(Rc::ptr_eq(&first, &second));
+
+        std::fs::write(path, "<template>ab</template>").unwrap();
+
+        let third = read_template(path, "", "Test", false).ok().unwrap();
+
+        std::fs::remove_file(path).unwrap();
+        assert!(!Rc::ptr_eq(&first, &third));
+    }
+
+    // The views! macro has its own copy of the list of URL attributes
+    #[test]
+    fn test_url_attributes() {
+        for name in &URL_ATTRIBUTES {
+            assert_eq!(attribute_context(name), Context::Url);
+        }
+    }
+
+    // The static parts of URLs are decoded the same way the escapers decode values
+    #[test]
+    fn test_decode_references() {
+        for text in &["a&amp;b", "&#106;&#x61&#X76;a", "x&colon;&Tab;&num;", "&#0;&#99999999;&#;&unknown;&", "&nbsp&lt;"] {
+            assert_eq!(decode_references(text), crate::escape::decode_references(text));
+        }
+
+        assert_eq!(decode_references("&#106;&#x61&colon;&amp&#0;"), "ja:&amp\u{FFFD}");
+    }
+
+    #[test]
+    fn test_unsupported() {
+        assert_eq!(interpret("<template><card /></template>", &[]), None);
+        assert_eq!(interpret("<template>{{ a.b }}</template>", &[("a", Value::Opaque)]), None);
+        assert_eq!(interpret("<template>{{ missing }}</template>", &[]), None);
+        assert_eq!(interpret("<template>{{ a * a }}</template>", &[("a", Value::Int(i128::MAX))]), None);
+        assert_eq!(interpret("<template>{{ a / 0 }}</template>", &[("a", Value::Int(1))]), None);
+    }
+
+    // The extra references are what picks `ViaValue` over `ViaOpaque`
+    #[allow(clippy::needless_borrow)]
+    #[test]
+    fn test_via_value() {
+        struct Custom;
+
+        let (name, custom) = ("John", Custom);
+
+        assert_eq!((&&Wrap(&name)).value(), Value::Str(Cow::Borrowed("John")));
+        assert_eq!((&&Wrap(&custom)).value(), Value::Opaque);
+    }
+}
@@ -0,0 +1,204 @@
+//! Translated messages written with `{{ t!("key", name = value) }}`.
+//!
+//! The messages are read from the catalogs in the `locales` folder given
+//! to `views!`, one `<locale>.toml` file per locale, and `views!` checks that
+//! every message exists in every locale and is given the arguments it uses.
+//! The locale of the messages is the one given to
+//! [`Render::render_in`](crate::Render::render_in), which passes it on to the
+//! components of the view, and the default locale of the catalogs otherwise.
+//! [`with_locale`] sets the locale used by the other methods of `Render`.
+
+use std::{
+    cell::RefCell,
+    fmt::{Display, Formatter, Result},
+};
+
+thread_local! {
+    static LOCALE: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Runs `f` with the given locale as the one used by [`Render::render`], so
+/// that the views rendered by it write their messages in that locale.
+///
+/// The locale is kept in a thread local, so it only applies to the views
+/// rendered on the current thread while `f` runs. Use
+/// [`Render::render_in`] to render a view in a locale from anywhere else,
+/// like a task which can move between threads.
+///
+/// [`Render::render`]: crate::Render::render
+/// [`Render::render_in`]: crate::Render::render_in
+///
+/// ```
+/// use reign_view::i18n::{locale, with_locale};
+///
+/// assert_eq!(with_locale("ru", || locale()), Some("ru".to_string()));
+/// assert_eq!(locale(), None);
+/// ```
+pub fn with_locale<T>(locale: &str, f: impl FnOnce() -> T) -> T {
+    // Restores the previous locale even if `f` panics
+    struct Restore(Option<String>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+
+            LOCALE.with(|locale| *locale.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(LOCALE.with(|current| current.replace(Some(locale.to_string()))));
+
+    f()
+}
+
+/// The locale set with [`with_locale`] on this thread, if any.
+pub fn locale() -> Option<String> {
+    LOCALE.with(|locale| locale.borrow().clone())
+}
+
+/// Piece of a message, either text or the name of an argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    Text(&'static str),
+    Arg(&'static str),
+}
+
+/// Messages of a locale, sorted by key.
+type Messages = &'static [(&'static str, &'static [Part])];
+
+/// Messages of every locale, generated by `views!` as the `CATALOG` static.
+#[derive(Debug)]
+pub struct Catalog {
+    locales: &'static [(&'static str, Messages)],
+    default: usize,
+}
+
+impl Catalog {
+    #[doc(hidden)]
+    pub const fn new(locales: &'static [(&'static str, Messages)], default: usize) -> Self {
+        Catalog { locales, default }
+    }
+
+    /// Locales which have messages, the default one being first.
+    pub fn locales(&self) -> Vec<&'static str> {
+        let default = self.locales[self.default].0;
+
+        Some(default)
+            .into_iter()
+            .chain(self.locales.iter().map(|(locale, _)| *locale).filter(|locale| *locale != default))
+            .collect()
+    }
+
+    /// The given locale if there are messages for it or for its language,
+    /// like `pt` for `pt-BR`, and the default locale otherwise.
+    fn find(&self, locale: Option<&str>) -> usize {
+        let current = match locale {
+            Some(current) => current.replace('_', "-"),
+            None => return self.default,
+        };
+        let language = current.split('-').next().unwrap_or_default();
+        let find = |name: &str| self.locales.iter().position(|(locale, _)| locale.eq_ignore_ascii_case(name));
+
+        find(&current).or_else(|| find(language)).unwrap_or(self.default)
+    }
+
+    fn get(&self, locale: usize, key: &str) -> Option<&'static [Part]> {
+        let messages = self.locales[locale].1;
+
+        messages
+            .binary_search_by_key(&key, |(key, _)| key)
+            .ok()
+            .map(|i| messages[i].1)
+    }
+}
+
+/// Message of the catalog written in the given locale.
+pub struct Message<'a> {
+    catalog: &'a Catalog,
+    locale: Option<&'a str>,
+    key: &'a str,
+    args: &'a [(&'a str, &'a dyn Display)],
+}
+
+impl<'a> Message<'a> {
+    pub fn new(
+        catalog: &'a Catalog,
+        locale: Option<&'a str>,
+        key: &'a str,
+        args: &'a [(&'a str, &'a dyn Display)],
+    ) -> Self {
+        Message {
+            catalog,
+            locale,
+            key,
+            args,
+        }
+    }
+}
+
+impl Display for Message<'_> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let parts = self
+            .catalog
+            .get(self.catalog.find(self.locale), self.key)
+            .or_else(|| self.catalog.get(self.catalog.default, self.key));
+
+        // Messages are checked by `views!`, so this is only for catalogs built by hand
+        let parts = match parts {
+            Some(parts) => parts,
+            None => return f.write_str(self.key),
+        };
+
+        for part in parts {
+            match part {
+                Part::Text(text) => f.write_str(text)?,
+                Part::Arg(name) => {
+                    if let Some((_, value)) = self.args.iter().find(|(arg, _)| arg == name) {
+                        value.fmt(f)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static CATALOG: Catalog = Catalog::new(
+        &[
+            ("en", &[("cart.items", &[Part::Arg("count"), Part::Text(" items")]), ("hi", &[Part::Text("Hi")])]),
+            ("pt-BR", &[("cart.items", &[Part::Arg("count"), Part::Text(" itens")])]),
+        ],
+        0,
+    );
+
+    #[test]
+    fn test_message() {
+        let message = |locale| Message::new(&CATALOG, locale, "cart.items", &[("count", &3)]).to_string();
+
+        assert_eq!(message(None), "3 items");
+        assert_eq!(message(Some("pt-BR")), "3 itens");
+        assert_eq!(message(Some("PT_br")), "3 itens");
+        assert_eq!(message(Some("fr")), "3 items");
+        assert_eq!(Message::new(&CATALOG, Some("pt-BR"), "hi", &[]).to_string(), "Hi");
+        assert_eq!(CATALOG.locales(), vec!["en", "pt-BR"]);
+    }
+
+    #[test]
+    fn test_language() {
+        static CATALOG: Catalog = Catalog::new(&[("en", &[("a", &[Part::Text("a")])]), ("pt", &[("a", &[Part::Text("á")])])], 0);
+
+        assert_eq!(Message::new(&CATALOG, Some("pt-BR"), "a", &[]).to_string(), "á");
+        assert_eq!(with_locale("en", || with_locale("pt", locale)), Some("pt".to_string()));
+        assert_eq!(with_locale("pt", || std::thread::spawn(locale).join().unwrap()), None);
+    }
+
+    #[test]
+    fn test_missing_message() {
+        assert_eq!(Message::new(&CATALOG, Some("pt-BR"), "bye", &[]).to_string(), "bye");
+    }
+}
@@ -3,35 +3,26 @@
 #![doc = include_str!("../README.md")]
 
 #[doc(hidden)]
-pub use maplit;
-
+pub mod attr;
+#[doc(hidden)]
+pub mod binding;
 #[doc(hidden)]
-pub fn encode_attribute_data(input: &str) -> String {
-    let mut out = String::with_capacity(input.len() + 2);
-    out.push('"');
-    for c in input.chars() {
-        match c {
-            '"' => out.push_str("&#x22;"),
-            _ => out.push(c),
-        }
-    }
-    out.push('"');
-    out
-}
+pub mod escape;
+pub mod filters;
+#[cfg(feature = "hot-reload")]
+pub mod hot;
+pub mod i18n;
+mod looping;
+mod render;
+mod slots;
+
+pub use attr::{AttrValue, Attrs};
+pub use binding::{ClassList, ClassValue, StyleList, StyleValue};
+pub use escape::{AttributeEscaper, Context, Escape, Escaper, HtmlEscaper, NoEscaper, Safe, SafeHtml, XmlEscaper};
+pub use filters::{Filters, StdFilters};
+pub use looping::Loop;
+pub use render::Render;
+pub use slots::{Slot, Slots};
 
 #[doc(hidden)]
-// Based on https://cheatsheetseries.owasp.org/cheatsheets/Cross_Site_Scripting_Prevention_Cheat_Sheet.html#rule-1-html-encode-before-inserting-untrusted-data-into-html-element-content
-pub fn encode_text(input: &str) -> String {
-    let mut out = String::with_capacity(input.len());
-    for c in input.chars() {
-        match c {
-            '&' => out.push_str("&amp;"),
-            '<' => out.push_str("&lt;"),
-            '>' => out.push_str("&gt;"),
-            '\'' => out.push_str("&#x27;"),
-            '"' => out.push_str("&quot;"),
-            _ => out.push(c),
-        }
-    }
-    out
-}
+pub use maplit;
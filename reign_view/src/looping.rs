@@ -0,0 +1,11 @@
+/// Metadata of the current iteration of a `!for` loop, available
+/// as `loop` inside of the element with the loop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Loop {
+    /// Number of the iteration, starting from zero
+    pub index: usize,
+    /// Whether this is the first iteration
+    pub first: bool,
+    /// Whether this is the last iteration
+    pub last: bool,
+}
@@ -0,0 +1,76 @@
+use std::{fmt, io};
+
+/// A view which can be rendered into any writer.
+///
+/// The `views!` macro implements this trait for every view it generates,
+/// along with [`std::fmt::Display`] which delegates to it.
+pub trait Render {
+    /// Writes the view into the given writer with its messages in the given
+    /// locale, or in the default locale of the catalogs when it is `None`.
+    /// The components and slots of the view are written in the same locale.
+    fn render_in(&self, w: &mut dyn fmt::Write, locale: Option<&str>) -> fmt::Result;
+
+    /// Writes the view into the given writer, with its messages in the locale
+    /// set with [`with_locale`](crate::i18n::with_locale) on this thread.
+    fn render(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        self.render_in(w, crate::i18n::locale().as_deref())
+    }
+
+    /// Estimated length of the rendered view in bytes, used to
+    /// preallocate buffers. Only the static content is counted.
+    fn size_hint(&self) -> usize {
+        0
+    }
+
+    /// Writes the view into the given [`std::fmt::Write`] sink, e.g. a `String`.
+    fn render_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result
+    where
+        Self: Sized,
+    {
+        self.render(w)
+    }
+
+    /// Writes the view into the given [`std::io::Write`] sink, e.g. a file,
+    /// a socket or a response body, without an intermediate `String`.
+    fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()>
+    where
+        Self: Sized,
+    {
+        let mut adapter = IoWriter {
+            inner: w,
+            error: None,
+        };
+
+        match self.render(&mut adapter) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(adapter
+                .error
+                .unwrap_or_else(|| io::Error::new(io::ErrorKind::Other, "view failed to render"))),
+        }
+    }
+
+    /// Renders the view into a new `String`.
+    fn render_to_string(&self) -> String {
+        let mut out = String::with_capacity(self.size_hint());
+
+        self.render(&mut out)
+            .expect("a Display implementation returned an error unexpectedly");
+        out
+    }
+}
+
+/// Adapter writing into an [`std::io::Write`] sink which keeps the
+/// underlying error since [`std::fmt::Error`] can not carry it.
+struct IoWriter<'a, W> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
@@ -0,0 +1,27 @@
+use std::fmt::{Result, Write};
+
+/// Content passed by a parent view to one of the `<slot>` elements of a component.
+pub type Slot<'a> = &'a dyn Fn(&mut dyn Write) -> Result;
+
+/// Named slots filled by the parent view when rendering a component.
+///
+/// The children of a component which are not wrapped in a `<template #name>`
+/// element are passed as the `default` slot.
+#[derive(Clone, Copy, Default)]
+pub struct Slots<'a> {
+    slots: &'a [(&'static str, Slot<'a>)],
+}
+
+impl<'a> Slots<'a> {
+    pub fn new(slots: &'a [(&'static str, Slot<'a>)]) -> Self {
+        Slots { slots }
+    }
+
+    /// Returns the slot with the given name if the parent view provided it.
+    pub fn get(&self, name: &str) -> Option<Slot<'a>> {
+        self.slots
+            .iter()
+            .find(|(slot_name, _)| *slot_name == name)
+            .map(|(_, slot)| *slot)
+    }
+}
@@ -0,0 +1,51 @@
+use reign::prelude::*;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+views!("src", "views");
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[test]
+fn test_render_does_not_allocate() {
+    let page = views::render::Page {
+        count: 3,
+        items: &["Inbox", "Drafts <archived>"],
+        title: "Messages",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+    let card = views::slots::Named {
+        title: "Hello",
+        body: "World",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+    let mut buffer = String::with_capacity(4096);
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    page.render_to(&mut buffer).unwrap();
+    card.render_to(&mut buffer).unwrap();
+    let after = ALLOCATIONS.load(Ordering::SeqCst);
+
+    assert_eq!(after - before, 0);
+}
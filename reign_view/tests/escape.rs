@@ -0,0 +1,291 @@
+// Property tests checking that no value can break out of the context it
+// is written into, whatever characters it contains.
+
+use proptest::prelude::*;
+use reign::prelude::*;
+use reign_view::{AttributeEscaper, Context, Escaper, HtmlEscaper, NoEscaper, XmlEscaper};
+use std::marker::PhantomData;
+
+views!("src", "views", "escape", module = html);
+views!("src", "views", "mail", module = mail, extension = "txt", escaper = ::reign::view::NoEscaper);
+
+fn escape(escaper: &dyn Escaper, context: Context, value: &str) -> String {
+    let mut out = String::new();
+
+    escaper.escape(&mut out, context, &value).unwrap();
+    out
+}
+
+/// Any string, with the characters special in HTML, scripts, styles and
+/// URLs being more likely than the others.
+fn value() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<String>(),
+        "[<>&\"'`=/\\\\:;(){}#?% \t\n\u{0}-\u{1F}a-zA-Z0-9é😀]{0,32}",
+    ]
+}
+
+/// `javascript:` with any of its characters written in another case or as a
+/// character reference, like `&#106;`, `&#x61;`, `&#118` or `&colon;`.
+fn encoded_javascript() -> impl Strategy<Value = String> {
+    let chars: Vec<_> = "javascript:"
+        .chars()
+        .map(|c| {
+            // The `;` can only be left out when no digit follows, so not on the colon
+            let (named, unterminated) = match c {
+                ':' => ("&colon;".to_string(), format!("&#{};", c as u32)),
+                _ => (c.to_ascii_uppercase().to_string(), format!("&#0{}", c as u32)),
+            };
+
+            prop_oneof![
+                Just(c.to_string()),
+                Just(named),
+                Just(format!("&#{};", c as u32)),
+                Just(unterminated),
+                Just(format!("&#x{:x};", c as u32)),
+                Just(format!("&#X{:02X};", c as u32)),
+            ]
+        })
+        .collect();
+
+    chars.prop_map(|chars| chars.concat())
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        let end = rest[start..].find(';').expect("unterminated entity") + start;
+
+        out.push_str(&rest[..start]);
+        out.push(match &rest[start + 1..end] {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            hex => char::from_u32(u32::from_str_radix(&hex[2..], 16).unwrap()).unwrap(),
+        });
+        rest = &rest[end + 1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn decode_script(s: &str) -> String {
+    let mut units = vec![];
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        if let Some(hex) = rest.strip_prefix("\\x") {
+            units.push(u16::from_str_radix(&hex[..2], 16).unwrap());
+            rest = &hex[2..];
+        } else if let Some(hex) = rest.strip_prefix("\\u") {
+            units.push(u16::from_str_radix(&hex[..4], 16).unwrap());
+            rest = &hex[4..];
+        } else {
+            units.push(rest.as_bytes()[0] as u16);
+            rest = &rest[1..];
+        }
+    }
+
+    String::from_utf16(&units).unwrap()
+}
+
+fn decode_style(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+
+    while let Some(start) = rest.find('\\') {
+        let end = rest[start..].find(' ').unwrap() + start;
+
+        out.push_str(&rest[..start]);
+        out.push(char::from_u32(u32::from_str_radix(&rest[start + 1..end], 16).unwrap()).unwrap());
+        rest = &rest[end + 1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn decode_percent(s: &str) -> String {
+    let mut bytes = vec![];
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        if let Some(hex) = rest.strip_prefix('%') {
+            bytes.push(u8::from_str_radix(&hex[..2], 16).unwrap());
+            rest = &hex[2..];
+        } else {
+            bytes.push(rest.as_bytes()[0]);
+            rest = &rest[1..];
+        }
+    }
+
+    String::from_utf8(bytes).unwrap()
+}
+
+/// Tags, attribute names and the characters delimiting the scripts and
+/// styles in the markup, which a value must not be able to change.
+fn structure(html: &str) -> Vec<String> {
+    let mut out = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+
+        let end = rest.find(|c: char| c == ' ' || c == '>').unwrap();
+        let name = &rest[..end];
+
+        out.push(format!("<{}", name));
+        rest = &rest[end..];
+
+        while let Some(attr) = rest.strip_prefix(' ') {
+            let eq = attr.find('=').unwrap();
+            let attr_name = &attr[..eq];
+            let value_end = attr[eq + 2..].find('"').unwrap() + eq + 2;
+            let value = &attr[eq + 2..value_end];
+
+            out.push(attr_name.to_string());
+
+            if attr_name.starts_with("on") || attr_name == "style" {
+                out.push(value.chars().filter(|c| "'\"();:{}".contains(*c)).collect());
+            }
+
+            rest = &attr[value_end + 1..];
+        }
+
+        rest = rest.strip_prefix('>').unwrap();
+
+        if name == "script" {
+            let end = rest.find("</script").unwrap();
+
+            out.push(rest[..end].chars().filter(|c| "'\"();".contains(*c)).collect());
+            rest = &rest[end..];
+        }
+    }
+
+    out
+}
+
+proptest! {
+    #[test]
+    fn text_is_encoded(s in value()) {
+        let out = escape(&HtmlEscaper, Context::Text, &s);
+
+        prop_assert!(!out.contains(|c| "<>\"'".contains(c)));
+        prop_assert_eq!(decode_entities(&out), s);
+    }
+
+    #[test]
+    fn attribute_keeps_quotes_closed(s in value()) {
+        let out = escape(&HtmlEscaper, Context::Attribute, &s);
+
+        prop_assert!(!out.contains('"'));
+        prop_assert_eq!(decode_entities(&out), s);
+    }
+
+    #[test]
+    fn script_is_encoded(s in value()) {
+        let out = escape(&HtmlEscaper, Context::Script, &s);
+
+        prop_assert!(out.chars().all(|c| c.is_ascii_alphanumeric() || c == '\\'));
+        prop_assert_eq!(decode_script(&out), s);
+    }
+
+    #[test]
+    fn style_is_encoded(s in value()) {
+        let out = escape(&HtmlEscaper, Context::Style, &s);
+
+        prop_assert!(out.chars().all(|c| c.is_ascii_alphanumeric() || c == '\\' || c == ' '));
+        prop_assert_eq!(decode_style(&out), s);
+    }
+
+    #[test]
+    fn url_part_is_encoded(s in value()) {
+        let out = escape(&HtmlEscaper, Context::UrlPart, &s);
+
+        prop_assert!(out.chars().all(|c| c.is_ascii_alphanumeric() || "-._~%".contains(c)));
+        prop_assert_eq!(decode_percent(&out), s);
+    }
+
+    #[test]
+    fn url_scheme_is_checked(
+        scheme in "[ \t\n\r]{0,2}[jJ][ \t\n\r]?[aA][vV][aA][sS][cC][rR][iI][pP][tT]",
+        s in value(),
+    ) {
+        let out = escape(&HtmlEscaper, Context::Url, &format!("{}:{}", scheme, s));
+
+        prop_assert_eq!(out, "about:invalid#blocked");
+    }
+
+    #[test]
+    fn url_encoded_scheme_is_checked(scheme in encoded_javascript(), s in value()) {
+        let out = escape(&HtmlEscaper, Context::Url, &format!("{}{}", scheme, s));
+
+        prop_assert_eq!(out, "about:invalid#blocked");
+    }
+
+    #[test]
+    fn url_keeps_quotes_closed(s in value()) {
+        let out = escape(&HtmlEscaper, Context::Url, &s);
+
+        prop_assert!(!out.contains('"'));
+    }
+
+    #[test]
+    fn strict_attribute_is_encoded(s in value()) {
+        for context in [Context::Text, Context::Attribute] {
+            let out = escape(&AttributeEscaper, context, &s);
+
+            prop_assert!(out.chars().all(|c| !c.is_ascii() || c.is_ascii_alphanumeric() || "&#;".contains(c)));
+            prop_assert_eq!(decode_entities(&out), s.clone());
+        }
+    }
+
+    #[test]
+    fn xml_is_encoded(s in value()) {
+        let out = escape(&XmlEscaper, Context::Text, &s);
+        let allowed = |c: char| matches!(c, '\t' | '\n' | '\r') || (c >= ' ' && c != '\u{FFFE}' && c != '\u{FFFF}');
+
+        prop_assert!(!out.contains(|c| "<>\"'".contains(c)));
+        prop_assert!(out.chars().all(allowed));
+
+        if s.chars().all(allowed) {
+            prop_assert_eq!(decode_entities(&out), s);
+        }
+    }
+
+    #[test]
+    fn no_escaper_is_identity(s in value()) {
+        prop_assert_eq!(escape(&NoEscaper, Context::Text, &s), s);
+    }
+
+    #[test]
+    fn views_keep_their_structure(s in value()) {
+        let render = |value: &str| {
+            html::Probe {
+                value,
+                slots: Default::default(),
+                marker: PhantomData,
+            }
+            .to_string()
+        };
+
+        prop_assert_eq!(structure(&render(&s)), structure(&render("")));
+    }
+}
+
+#[test]
+fn test_escaper_option() {
+    let view = mail::Welcome {
+        name: "Tom & Jerry",
+        site: "<Reign>",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    assert_eq!(view.to_string(), "Hi Tom & Jerry, welcome to \"<Reign>\" & enjoy!");
+}
@@ -0,0 +1,581 @@
+use reign::prelude::*;
+use std::marker::PhantomData;
+
+views!("src", "views");
+
+#[test]
+fn test_named_and_default_slots() {
+    let view = views::slots::Named {
+        title: "Hello",
+        body: "World",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    assert_eq!(
+        view.to_string(),
+        "<div class=\"card\"><h1>Hello</h1>\n  \n  <p>World</p>\n</div>"
+    );
+}
+
+#[test]
+fn test_slot_fallback() {
+    let view = views::slots::Fallback {
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    assert_eq!(view.to_string(), "<div class=\"card\"><h1>Untitled</h1></div>");
+}
+
+#[test]
+fn test_optional_props() {
+    let view = views::props::Defaults::new();
+
+    assert_eq!(
+        view.to_string(),
+        "<i width=\"16\">icon</i> <i width=\"32\">starHi</i>"
+    );
+}
+
+#[test]
+fn test_constructor_defaults() {
+    let view = views::shared::Icon::new();
+
+    assert_eq!(view.size, 16);
+    assert_eq!(view.label, "icon");
+    assert_eq!(view.title, None);
+}
+
+#[test]
+fn test_forwarded_prop_types() {
+    // `size` is a `u32` because it is passed to the `size` field of `shared:icon`
+    let view = views::props::Forward::new(24);
+
+    assert_eq!(view.to_string(), "<i width=\"24\">icon</i>");
+}
+
+#[test]
+fn test_spread_and_forwarded_attributes() {
+    let icon = views::shared::Icon {
+        size: 24,
+        label: "star",
+        title: None,
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+    let view = views::props::Spread {
+        icon: &icon,
+        disabled: true,
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    assert_eq!(
+        view.to_string(),
+        "<i width=\"24\">starHi</i>\n<button class=\"btn primary\" id=\"go\" disabled aria-label=\"Go on\" \
+         style=\"color: red\">Go</button>"
+    );
+    assert_eq!(views::shared::Button::new("Go").to_string(), "<button class=\"btn\">Go</button>");
+}
+
+#[test]
+fn test_render_to_writers() {
+    let view = views::slots::Named {
+        title: "Hello",
+        body: "World",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+    let expected = "<div class=\"card\"><h1>Hello</h1>\n  \n  <p>World</p>\n</div>";
+
+    let mut string = String::new();
+    view.render_to(&mut string).unwrap();
+    assert_eq!(string, expected);
+
+    let mut bytes = vec![];
+    view.write_to(&mut bytes).unwrap();
+    assert_eq!(bytes, expected.as_bytes());
+
+    assert_eq!(view.render_to_string(), expected);
+}
+
+#[test]
+fn test_size_hint() {
+    let view = views::shared::Card::new();
+
+    assert_eq!(view.size_hint(), "<div class=\"card\"><h1>Untitled</h1></div>".len());
+
+    // Only the static content of the view is counted, not of its components
+    assert_eq!(views::slots::Fallback::new().size_hint(), 0);
+}
+
+mod ui {
+    use reign::prelude::*;
+
+    views!(
+        "tests",
+        "ui",
+        module = pages,
+        root = crate::ui,
+        extension = "htm",
+        style_const = false,
+        case = "kebab",
+    );
+}
+
+#[test]
+fn test_options() {
+    let view = ui::pages::user_cards::SmallCard::new("John");
+
+    assert_eq!(view.to_string(), "<div><b>John</b></div>");
+}
+
+mod shouting {
+    use reign::prelude::*;
+    use reign_view::{filters, Filters};
+    use std::fmt::Display;
+
+    views!("src", "views", "filters", module = posts, root = crate::shouting, filters = crate::shouting::Shouting);
+
+    pub struct Shouting;
+
+    impl Filters for Shouting {
+        fn upper<T: Display + ?Sized>(&self, value: &T) -> String {
+            format!("{}!", filters::upper(value))
+        }
+    }
+}
+
+#[test]
+fn test_filters() {
+    let published = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_600_000_000);
+    let view = views::filters::Post {
+        title: "Rust & templates",
+        tags: &["rust", "web"],
+        count: 1,
+        published,
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+    let shouting = shouting::posts::Post {
+        title: "Hi",
+        tags: &[],
+        count: 2,
+        published,
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    assert_eq!(
+        view.to_string(),
+        "<h1 title=\"RUST &amp; TEMPLATES\">RUST &amp; TEMPL...</h1>\n\
+         <p>rust, web (1 comment)</p>\n<time>2020-09-13</time>"
+    );
+    assert_eq!(
+        shouting.to_string(),
+        "<h1 title=\"HI!\">HI!</h1>\n<p> (2 comments)</p>\n<time>2020-09-13</time>"
+    );
+}
+
+mod translated {
+    use reign::prelude::*;
+
+    views!("tests", "i18n", module = i18n, root = crate::translated, locales = "tests/locales");
+}
+
+#[test]
+fn test_translated_messages() {
+    use reign_view::i18n::with_locale;
+
+    let view = translated::i18n::Checkout::new(12);
+
+    assert_eq!(
+        view.to_string(),
+        "<h1>Checkout &amp; pay</h1>\n<p title=\"Prices include &#x22;VAT&#x22;\">Total: 12 €</p>"
+    );
+    assert_eq!(
+        with_locale("ru-RU", || view.to_string()),
+        "<h1>Оформление заказа</h1>\n<p title=\"Цены включают НДС\">Итого: 12 €</p>"
+    );
+    assert_eq!(translated::i18n::CATALOG.locales(), vec!["en", "ru"]);
+}
+
+#[test]
+fn test_render_in_locale() {
+    use reign_view::i18n::with_locale;
+
+    fn render(locale: Option<&str>) -> String {
+        let mut out = String::new();
+
+        translated::i18n::Order::new(12).render_in(&mut out, locale).unwrap();
+        out
+    }
+
+    // Given to the components, whichever thread renders the view
+    assert_eq!(
+        with_locale("en", || std::thread::spawn(|| render(Some("ru"))).join().unwrap()),
+        "<h1>Оформление заказа</h1>\n<p title=\"Цены включают НДС\">Итого: 12 €</p>"
+    );
+    assert_eq!(with_locale("ru", || render(None)), translated::i18n::Checkout::new(12).to_string());
+}
+
+mod minified {
+    use reign::prelude::*;
+
+    views!("tests", "minify", module = minified, root = crate::minified, minify = true);
+}
+
+#[test]
+fn test_minified_view() {
+    let view = minified::minified::Article::new(&["rust", "web"], "Hello");
+
+    assert_eq!(
+        view.to_string(),
+        "<article> <h1> Hello </h1> <!--[if IE]><p>Please update your browser</p><![endif]--> \
+         <p> Tags: <span>rust</span><span>web</span> </p> <pre>\n  let x = 1;\n    </pre> </article>"
+    );
+}
+
+#[test]
+fn test_fragment_and_named_templates() {
+    let view = views::blocks::List::new();
+
+    assert_eq!(
+        view.to_string(),
+        "<b>First</b><h1>Second</h1><p>Text</p>"
+    );
+    assert!(views::SCRIPT.contains("console.log(\"card\");"));
+}
+
+#[test]
+fn test_template_comments() {
+    let view = views::render::Notes::new(&["a", "b"]);
+
+    assert_eq!(
+        view.to_string(),
+        "\n  \n  <ul>\n    <!-- notes -->\n    <li>a</li><li>b</li>\n  </ul>\n  <p title=\"Notes\"></p>\n"
+    );
+}
+
+#[test]
+fn test_escaped_expressions() {
+    let view = views::render::Page {
+        count: 3,
+        items: &["<b>one</b>", "two & three"],
+        title: "\"Inbox\"",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+    let html = view.to_string();
+
+    assert!(html.contains("<title>&quot;Inbox&quot;</title>"));
+    assert!(html.contains("<main data-count=\"3\">"));
+    assert!(html.contains("<p>You have 3 new messages.</p>"));
+    assert!(html.contains("<li>&lt;b&gt;one&lt;/b&gt;</li><li>two &amp; three</li>"));
+}
+
+#[test]
+fn test_safe_markup_and_nested_views() {
+    let view = views::render::Markup {
+        card: views::shared::Card::new(),
+        html: reign::view::Safe("<em>\"hi\"</em>".to_string()),
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    assert_eq!(
+        view.to_string(),
+        "<div title=\"<em>&#x22;hi&#x22;</em>\"><em>\"hi\"</em><div class=\"card\"><h1>Untitled</h1></div></div>"
+    );
+}
+
+#[test]
+fn test_escaping_contexts() {
+    let view = views::render::Contexts {
+        color: "red;}",
+        event: "click",
+        query: "a&b'",
+        url: "javascript:alert(1)",
+        scheme: "javascript",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    assert_eq!(
+        view.to_string(),
+        "<a href=\"about:invalid#blocked\" title=\"javascript:alert(1)\">link</a>\
+         <a href=\"about:invalid#blocked\" onclick=\"a\\x26b\\x27\">link</a>\
+         <a href=\"/search?q=a%26b%27\" onclick=\"go(\'a\\x26b\\x27\')\" style=\"color: red\\3B \\7D \">search</a>\
+         <a href=\"about:invalid#blocked\">split</a>\
+         <a href=\"javajavascript%3Aalert%281%29?a=1&amp;b=2\">prefix</a>\
+         <script>var query = \"a\\x26b\\x27\";</script>"
+    );
+}
+
+#[test]
+fn test_encoded_url_schemes() {
+    for url in &["javascript&#58;alert(1)", "javascript&colon;alert(1)", "&#106;avascript:alert(1)"] {
+        let view = views::render::Contexts {
+            color: "",
+            event: "click",
+            query: "",
+            url,
+            scheme: "https",
+            slots: Default::default(),
+            marker: PhantomData,
+        };
+
+        assert_eq!(view.to_string().matches("<a href=\"about:invalid#blocked\"").count(), 2);
+    }
+}
+
+#[test]
+fn test_invalid_attribute_names() {
+    for event in &["click=\"alert(1)\" x", "click><script>alert(1)</script", "click x"] {
+        let view = views::render::Contexts {
+            color: "",
+            event,
+            query: "a",
+            url: "/",
+            scheme: "https",
+            slots: Default::default(),
+            marker: PhantomData,
+        };
+
+        assert!(view.to_string().starts_with("<a href=\"/\" title=\"/\">link</a><a href=\"/\">link</a>"));
+    }
+}
+
+#[test]
+fn test_boolean_and_optional_attributes() {
+    let mut view = views::render::Inputs {
+        disabled: true,
+        checked: false,
+        title: None,
+        max: Some(8),
+        value: "\"x\"",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    assert_eq!(view.to_string(), "<input disabled maxlength=\"8\" value=\"&#x22;x&#x22;\">");
+
+    view.disabled = false;
+    view.title = Some("Name");
+    view.max = None;
+
+    assert_eq!(view.to_string(), "<input title=\"Name\" value=\"&#x22;x&#x22;\">");
+}
+
+#[test]
+fn test_class_and_style_bindings() {
+    let view = views::render::Bindings {
+        classes: &[("big", true), ("active", true), ("hidden", false)],
+        styles: &[("z-index", 2), ("margin", 1)],
+        wide: true,
+        color: "red; position: fixed",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    assert_eq!(
+        view.to_string(),
+        "<button class=\"btn active big\" style=\"margin: 1; z-index: 2\">Go</button>\
+         <i class=\"icon wide\" style=\"color: red\"></i>\
+         <b style=\"color: red\\3B \\20 position\\3A \\20 fixed; top: 0; margin: 0\">!</b>"
+    );
+}
+
+#[test]
+fn test_layout() {
+    let home = views::pages::Home {
+        user: "Tom",
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+    let about = views::pages::About {
+        slots: Default::default(),
+        marker: PhantomData,
+    };
+
+    assert_eq!(
+        home.to_string(),
+        "<!DOCTYPE html><html><head><title>Home</title></head>\
+         <body><nav>Tom</nav><p>Welcome Tom</p></body></html>"
+    );
+    assert_eq!(
+        about.to_string(),
+        "<!DOCTYPE html><html><head><title>Reign</title><meta name=\"robots\" content=\"none\"></head>\
+         <body><nav>Guest</nav>About</body></html>"
+    );
+}
+
+mod hot {
+    use reign::prelude::*;
+
+    views!("tests", "hot", root = crate::hot, hot_reload = true);
+}
+
+/// Copy of the hot views in a temporary directory, so that the tests
+/// can change the templates without touching the crate.
+struct HotDir(std::path::PathBuf);
+
+impl Drop for HotDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn test_hot_reload() {
+    let dir = HotDir(std::env::temp_dir().join(format!("reign_view_hot_{}", std::process::id())));
+    let hot_dir = dir.0.join("tests").join("hot");
+
+    std::fs::create_dir_all(&hot_dir).unwrap();
+
+    for file in std::fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/hot")).unwrap() {
+        let file = file.unwrap();
+
+        std::fs::copy(file.path(), hot_dir.join(file.file_name())).unwrap();
+    }
+
+    reign::view::hot::set_base_dir(&dir.0);
+
+    // Only this test renders the banner, so changing it doesn't affect the others
+    let view = hot::views::Banner::new(true, "John");
+
+    assert_eq!(view.to_string(), "<p>Hello John</p><b>!</b>");
+
+    std::fs::write(
+        hot_dir.join("banner.html"),
+        "<template><h1 !if=\"admin\">Welcome back {{ name }}</h1><p !else>Hi</p></template>",
+    )
+    .unwrap();
+
+    if cfg!(debug_assertions) {
+        assert_eq!(view.to_string(), "<h1>Welcome back John</h1>");
+    } else {
+        assert_eq!(view.to_string(), "<p>Hello John</p><b>!</b>");
+    }
+}
+
+#[test]
+fn test_hot_reload_fallback() {
+    // Components can not be interpreted so the compiled template is used
+    let view = hot::views::Card::new("John");
+
+    assert_eq!(view.to_string(), "<div><p title=\"John\">Hello John</p></div>");
+}
+
+#[test]
+fn test_scoped_style() {
+    let badge = views::styles::Badge::new("New").to_string();
+    let scope = badge.split(' ').nth(1).unwrap();
+
+    assert!(scope.starts_with("data-r-"));
+    assert_eq!(
+        badge,
+        format!("<span {0} class=\"badge\"><b {0}>New</b></span>", scope)
+    );
+    assert_eq!(
+        views::styles::BadgeIcon::new().to_string(),
+        format!("<i {} class=\"icon\"></i>", scope)
+    );
+    assert!(views::STYLE.contains(&format!(
+        ".badge > b[{0}], .badge[{0}]::after {{font-weight: bold;}}\nbody .badge[{0}] {{color: red;}}\nbody {{\n    margin: 0;\n  }}\n",
+        scope
+    )));
+}
+
+#[test]
+fn test_match() {
+    use std::cmp::Ordering;
+
+    let view = |order, result, state| {
+        views::control::Status {
+            order,
+            result,
+            state,
+            slots: Default::default(),
+            marker: PhantomData,
+        }
+        .to_string()
+    };
+
+    assert_eq!(
+        view(Ordering::Less, Ok(0), "paid"),
+        "\n  <p><b>none</b></p>\n  <span>less</span>\n  <em>paid</em>\n"
+    );
+    assert_eq!(
+        view(Ordering::Equal, Ok(12), "open"),
+        "\n  <p><i>many 12</i></p>\n  <span>more</span>\n  <em>open</em>\n"
+    );
+    assert_eq!(
+        view(Ordering::Greater, Err("failed"), "paid"),
+        "\n  <p><u>failed</u></p>\n  <span>more</span>\n  <em>paid</em>\n"
+    );
+    assert_eq!(
+        view(Ordering::Less, Ok(2), "paid"),
+        "\n  <p>few</p>\n  <span>less</span>\n  <em>paid</em>\n"
+    );
+    assert_eq!(
+        view(Ordering::Less, Ok(5), "paid"),
+        "\n  <p>5</p>\n  <span>less</span>\n  <em>paid</em>\n"
+    );
+}
+
+#[test]
+fn test_control_flow_expressions() {
+    let view = |active, count, items| {
+        // Closure parameters and match bindings would be missing fields
+        views::control::Expressions {
+            active,
+            count,
+            items,
+            min: 10,
+            slots: Default::default(),
+            marker: PhantomData,
+        }
+        .to_string()
+    };
+
+    assert_eq!(
+        view(true, 3, &[("tea", 4), ("cake", 12)]),
+        "\n  <p>on</p>\n  <p>few</p>\n  <p>tea, cake</p>\n  <p>16</p>\n  <p>1</p>\n  <p>tea</p>\n  <p>7</p>\n"
+    );
+    assert_eq!(
+        view(false, 0, &[]),
+        "\n  <p>off</p>\n  <p>none</p>\n  <p></p>\n  <p>0</p>\n  <p>0</p>\n  <p>-</p>\n  <p>1</p>\n"
+    );
+}
+
+#[test]
+fn test_if_let() {
+    let view = |status, user| {
+        views::control::User {
+            status,
+            user,
+            slots: Default::default(),
+            marker: PhantomData,
+        }
+        .to_string()
+    };
+
+    assert_eq!(view(Ok(()), Some("John")), "<p>Hi John</p>");
+    assert_eq!(view(Err(404), None), "<p>Error 404</p>");
+    assert_eq!(view(Ok(()), None), "<p>Guest</p>");
+}
+
+#[test]
+fn test_loop_metadata_and_empty() {
+    assert_eq!(
+        views::control::List::new(&["a", "b"]).to_string(),
+        "<ul><li class=\"0\">a<b>first</b></li><li class=\"1\">b<i>last</i></li></ul>"
+    );
+    assert_eq!(
+        views::control::List::new(&["a"]).to_string(),
+        "<ul><li class=\"0\">a<b>first</b><i>last</i></li></ul>"
+    );
+    assert_eq!(views::control::List::new(&[]).to_string(), "<ul><li>nothing</li></ul>");
+}
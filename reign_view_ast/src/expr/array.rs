@@ -1,5 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
@@ -29,11 +29,3 @@ impl Parse for ExprArray {
         }
     }
 }
-
-impl Tokenize for ExprArray {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.bracket_token.surround(tokens, |tokens| {
-            self.elems.tokenize(tokens, idents, scopes);
-        })
-    }
-}
@@ -1,6 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     BinOp, Error,
@@ -24,11 +23,3 @@ impl Parse for ExprBinary {
         }
     }
 }
-
-impl Tokenize for ExprBinary {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.left.tokenize(tokens, idents, scopes);
-        self.op.to_tokens(tokens);
-        self.right.tokenize(tokens, idents, scopes);
-    }
-}
@@ -0,0 +1,75 @@
+use super::{super::pat::Pat, Expr};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream, Result},
+    token::{Brace, Colon, Eq, Let, Semi},
+    Token, Type,
+};
+
+/// `{ let a = b; c }`, whose `let` bindings are visible in the statements after them.
+pub struct ExprBlock {
+    pub brace_token: Brace,
+    pub stmts: Vec<Stmt>,
+}
+
+pub enum Stmt {
+    Local(Local),
+    /// Expression, followed by a semicolon unless it is the value of the block
+    Expr(Expr, Option<Semi>),
+}
+
+/// `let pat: type = expr;`
+pub struct Local {
+    pub let_token: Let,
+    pub pat: Pat,
+    pub ty: Option<(Colon, Box<Type>)>,
+    pub init: Option<(Eq, Box<Expr>)>,
+    pub semi_token: Semi,
+}
+
+impl Parse for ExprBlock {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let brace_token = braced!(content in input);
+        let mut stmts = vec![];
+
+        while !content.is_empty() {
+            if content.peek(Token![let]) {
+                stmts.push(Stmt::Local(content.parse()?));
+                continue;
+            }
+
+            let expr: Expr = content.parse()?;
+
+            if content.peek(Token![;]) {
+                stmts.push(Stmt::Expr(expr, Some(content.parse()?)));
+            } else if content.is_empty() || expr.is_block_like() {
+                stmts.push(Stmt::Expr(expr, None));
+            } else {
+                return Err(content.error("expected `;`"));
+            }
+        }
+
+        Ok(ExprBlock { brace_token, stmts })
+    }
+}
+
+impl Parse for Local {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Local {
+            let_token: input.parse()?,
+            pat: input.parse()?,
+            ty: if input.peek(Token![:]) {
+                Some((input.parse()?, Box::new(input.parse()?)))
+            } else {
+                None
+            },
+            init: if input.peek(Token![=]) {
+                Some((input.parse()?, Box::new(input.parse()?)))
+            } else {
+                None
+            },
+            semi_token: input.parse()?,
+        })
+    }
+}
@@ -1,5 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
@@ -30,12 +30,3 @@ impl Parse for ExprCall {
         }
     }
 }
-
-impl Tokenize for ExprCall {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.func.tokenize(tokens, idents, scopes);
-        self.paren_token.surround(tokens, |tokens| {
-            self.args.tokenize(tokens, idents, scopes);
-        })
-    }
-}
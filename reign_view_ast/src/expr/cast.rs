@@ -1,6 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     token::As,
@@ -25,13 +24,3 @@ impl Parse for ExprCast {
         }
     }
 }
-
-impl Tokenize for ExprCast {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        // Can't use casting for type identification because it tries to copy the variable
-
-        self.expr.tokenize(tokens, idents, scopes);
-        self.as_token.to_tokens(tokens);
-        self.ty.to_tokens(tokens);
-    }
-}
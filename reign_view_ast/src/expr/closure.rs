@@ -0,0 +1,74 @@
+use super::{super::pat::Pat, Expr};
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    punctuated::Punctuated,
+    token::{Colon, Comma, Move, Or, RArrow},
+    Token, Type,
+};
+
+/// `|a, b: u8| expr`, whose parameters are visible in its body only.
+pub struct ExprClosure {
+    pub move_token: Option<Move>,
+    pub or1_token: Or,
+    pub inputs: Punctuated<ClosureArg, Comma>,
+    pub or2_token: Or,
+    pub output: Option<(RArrow, Box<Type>)>,
+    pub body: Box<Expr>,
+}
+
+pub struct ClosureArg {
+    pub pat: Pat,
+    pub ty: Option<(Colon, Box<Type>)>,
+}
+
+impl Parse for ExprClosure {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let move_token = input.parse()?;
+        let or1_token = input.parse()?;
+        let mut inputs = Punctuated::new();
+
+        // `||` is read as two `|`
+        while !input.peek(Token![|]) {
+            inputs.push_value(input.parse()?);
+
+            if input.peek(Token![|]) {
+                break;
+            }
+
+            inputs.push_punct(input.parse()?);
+        }
+
+        let or2_token = input.parse()?;
+
+        // The body needs to be a block when the return type is given
+        let (output, body) = if input.peek(Token![->]) {
+            let output = (input.parse()?, Box::new(input.call(Type::without_plus)?));
+
+            (Some(output), Expr::Block(input.parse()?))
+        } else {
+            (None, input.parse()?)
+        };
+
+        Ok(ExprClosure {
+            move_token,
+            or1_token,
+            inputs,
+            or2_token,
+            output,
+            body: Box::new(body),
+        })
+    }
+}
+
+impl Parse for ClosureArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(ClosureArg {
+            pat: input.parse()?,
+            ty: if input.peek(Token![:]) {
+                Some((input.parse()?, Box::new(input.parse()?)))
+            } else {
+                None
+            },
+        })
+    }
+}
@@ -1,6 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     token::Dot,
@@ -30,11 +29,3 @@ impl Parse for ExprField {
         }
     }
 }
-
-impl Tokenize for ExprField {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.base.tokenize(tokens, idents, scopes);
-        self.dot_token.to_tokens(tokens);
-        self.member.to_tokens(tokens);
-    }
-}
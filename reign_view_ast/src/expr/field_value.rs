@@ -1,6 +1,4 @@
-use super::{is_member_named, Expr, Tokenize, ViewFields};
-use proc_macro2::TokenStream;
-use quote::{quote, ToTokens, TokenStreamExt};
+use super::{is_member_named, Expr};
 use syn::{
     parse::{Parse, ParseStream, Result},
     token::Colon,
@@ -41,21 +39,3 @@ impl Parse for FieldValue {
         })
     }
 }
-
-impl Tokenize for FieldValue {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        if let Some(colon_token) = &self.colon_token {
-            self.member.to_tokens(tokens);
-            colon_token.to_tokens(tokens);
-            self.expr.tokenize(tokens, idents, scopes);
-        } else {
-            // Member is always named
-            if let Member::Named(ident) = &self.member {
-                idents.push(ident.clone());
-                tokens.append_all(quote! {
-                    #ident: self.#ident
-                });
-            }
-        }
-    }
-}
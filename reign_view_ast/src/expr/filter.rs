@@ -0,0 +1,77 @@
+use super::Expr;
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream, Parser, Result},
+    punctuated::Punctuated,
+    token::{Comma, Paren},
+    Ident,
+};
+
+/// `expr | name(args)`, a call of the filter `name` from the filter registry.
+pub struct ExprFilter {
+    pub expr: Box<Expr>,
+    pub name: Ident,
+    pub args: Punctuated<Expr, Comma>,
+}
+
+/// Parses an expression followed by a pipeline of filters.
+///
+/// Only the `|` which are not inside parentheses, brackets or braces separate
+/// the filters, so a bitwise or needs parentheses, like `(a | b)`.
+pub fn filtered_expr(input: ParseStream) -> Result<Expr> {
+    let tokens: TokenStream = input.parse()?;
+    let mut segments = vec![TokenStream::new()];
+    let mut joint = false;
+
+    for tree in tokens {
+        let separator = match &tree {
+            TokenTree::Punct(p) => {
+                let separator = p.as_char() == '|' && p.spacing() == Spacing::Alone && !joint;
+
+                joint = p.spacing() == Spacing::Joint;
+                separator
+            }
+            _ => {
+                joint = false;
+                false
+            }
+        };
+
+        if separator {
+            segments.push(TokenStream::new());
+        } else {
+            segments.last_mut().unwrap().extend(Some(tree));
+        }
+    }
+
+    let mut segments = segments.into_iter();
+    let mut expr = Expr::parse.parse2(segments.next().unwrap())?;
+
+    for segment in segments {
+        let (name, args) = filter_call.parse2(segment)?;
+
+        expr = Expr::Filter(ExprFilter {
+            expr: Box::new(expr),
+            name,
+            args,
+        });
+    }
+
+    Ok(expr)
+}
+
+fn filter_call(input: ParseStream) -> Result<(Ident, Punctuated<Expr, Comma>)> {
+    let name = input.parse()?;
+
+    let args = if input.peek(Paren) {
+        let content;
+
+        parenthesized!(content in input);
+        content.parse_terminated(Expr::parse)?
+    } else {
+        Punctuated::new()
+    };
+
+    Ok((name, args))
+}
@@ -1,5 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{TokenStream, TokenTree};
+use super::Expr;
+use proc_macro2::TokenTree;
 use syn::{
     parse::{Parse, ParseStream, Result},
     parse2,
@@ -24,11 +24,3 @@ impl Parse for ExprGroup {
         Ok(ExprGroup { group_token, expr })
     }
 }
-
-impl Tokenize for ExprGroup {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.group_token.surround(tokens, |tokens| {
-            self.expr.tokenize(tokens, idents, scopes);
-        });
-    }
-}
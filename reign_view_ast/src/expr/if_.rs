@@ -0,0 +1,38 @@
+use super::{expr_no_struct, Expr, ExprBlock};
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    token::{Else, If},
+    Token,
+};
+
+/// `if cond { a } else { b }`, where the condition can be `let pat = expr`.
+pub struct ExprIf {
+    pub if_token: If,
+    pub cond: Box<Expr>,
+    pub then_branch: ExprBlock,
+    /// Either a block or another `if`
+    pub else_branch: Option<(Else, Box<Expr>)>,
+}
+
+impl Parse for ExprIf {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(ExprIf {
+            if_token: input.parse()?,
+            cond: Box::new(input.call(expr_no_struct)?),
+            then_branch: input.parse()?,
+            else_branch: if input.peek(Token![else]) {
+                let else_token = input.parse()?;
+
+                let branch = if input.peek(Token![if]) {
+                    Expr::If(input.parse()?)
+                } else {
+                    Expr::Block(input.parse()?)
+                };
+
+                Some((else_token, Box::new(branch)))
+            } else {
+                None
+            },
+        })
+    }
+}
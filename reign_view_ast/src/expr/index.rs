@@ -1,5 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     token::Bracket,
@@ -29,13 +29,3 @@ impl Parse for ExprIndex {
         }
     }
 }
-
-impl Tokenize for ExprIndex {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.expr.tokenize(tokens, idents, scopes);
-
-        self.bracket_token.surround(tokens, |tokens| {
-            self.index.tokenize(tokens, idents, scopes);
-        });
-    }
-}
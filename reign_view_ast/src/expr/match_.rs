@@ -0,0 +1,67 @@
+use super::{super::pat::Case, expr_no_struct, Expr};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream, Result},
+    token::{Brace, Comma, FatArrow, Match},
+    Token,
+};
+
+/// `match expr { pat => value, }`, whose arms bind by reference like the
+/// `!case` elements of `!match`.
+pub struct ExprMatch {
+    pub match_token: Match,
+    pub expr: Box<Expr>,
+    pub brace_token: Brace,
+    pub arms: Vec<Arm>,
+}
+
+pub struct Arm {
+    /// Patterns of the arm along with its guard
+    pub case: Case,
+    pub fat_arrow_token: FatArrow,
+    pub body: Box<Expr>,
+    pub comma: Option<Comma>,
+}
+
+impl Parse for ExprMatch {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let match_token = input.parse()?;
+        let expr = Box::new(input.call(expr_no_struct)?);
+        let content;
+        let brace_token = braced!(content in input);
+        let mut arms = vec![];
+
+        while !content.is_empty() {
+            arms.push(content.parse()?);
+        }
+
+        Ok(ExprMatch {
+            match_token,
+            expr,
+            brace_token,
+            arms,
+        })
+    }
+}
+
+impl Parse for Arm {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let case = input.parse()?;
+        let fat_arrow_token = input.parse()?;
+        let body: Expr = input.parse()?;
+
+        // Arms whose value is a block don't need a comma
+        let comma = if input.is_empty() || body.is_block_like() && !input.peek(Token![,]) {
+            None
+        } else {
+            Some(input.parse()?)
+        };
+
+        Ok(Arm {
+            case,
+            fat_arrow_token,
+            body: Box::new(body),
+            comma,
+        })
+    }
+}
@@ -0,0 +1,34 @@
+use super::Expr;
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    Ident, LitStr, Token,
+};
+
+/// `t!("key", name = value)`, a message of the catalogs given to `views!`.
+pub struct ExprMessage {
+    pub key: LitStr,
+    pub args: Vec<(Ident, Expr)>,
+}
+
+impl Parse for ExprMessage {
+    // Parses the contents of `t!(...)`
+    fn parse(input: ParseStream) -> Result<Self> {
+        let key = input.parse()?;
+        let mut args = vec![];
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+
+            if input.is_empty() {
+                break;
+            }
+
+            let name = input.parse()?;
+
+            input.parse::<Token![=]>()?;
+            args.push((name, input.parse()?));
+        }
+
+        Ok(ExprMessage { key, args })
+    }
+}
@@ -1,6 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
@@ -34,16 +33,3 @@ impl Parse for ExprMethodCall {
         }
     }
 }
-
-impl Tokenize for ExprMethodCall {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.receiver.tokenize(tokens, idents, scopes);
-        self.dot_token.to_tokens(tokens);
-        self.method.to_tokens(tokens);
-        self.turbofish.to_tokens(tokens);
-
-        self.paren_token.surround(tokens, |tokens| {
-            self.args.tokenize(tokens, idents, scopes);
-        });
-    }
-}
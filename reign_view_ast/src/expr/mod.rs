@@ -1,6 +1,5 @@
-use super::{is_member_named, tokenize::Tokenize, tokenize::ViewFields};
+use super::{is_member_named, pat::Let};
 use proc_macro2::{Delimiter, TokenStream, TokenTree};
-use quote::{quote, ToTokens, TokenStreamExt};
 use syn::{
     braced, bracketed, parenthesized,
     parse::{discouraged::Speculative, Parse, ParseStream, Result},
@@ -14,12 +13,18 @@ pub const KEYWORDS: [&str; 4] = ["Some", "None", "Ok", "Err"];
 
 mod array;
 mod binary;
+mod block;
 mod call;
 mod cast;
+mod closure;
 mod field;
 mod field_value;
+mod filter;
 mod group;
+mod if_;
 mod index;
+mod match_;
+mod message;
 mod method_call;
 mod paren;
 mod range;
@@ -30,34 +35,48 @@ mod tuple;
 mod type_;
 mod unary;
 
-use array::ExprArray;
-use binary::ExprBinary;
-use call::ExprCall;
-use cast::ExprCast;
-use field::ExprField;
-use field_value::FieldValue;
-use group::ExprGroup;
-use index::ExprIndex;
-use method_call::ExprMethodCall;
-use paren::ExprParen;
-use range::ExprRange;
-use reference::ExprReference;
-use repeat::ExprRepeat;
-use struct_::ExprStruct;
-use tuple::ExprTuple;
-use type_::ExprType;
-use unary::ExprUnary;
-
-// TODO:(view:expr) ExprIf
+pub use array::ExprArray;
+pub use binary::ExprBinary;
+pub use block::{ExprBlock, Local, Stmt};
+pub use call::ExprCall;
+pub use cast::ExprCast;
+pub use closure::{ClosureArg, ExprClosure};
+pub use field::ExprField;
+pub use field_value::FieldValue;
+pub use filter::{filtered_expr, ExprFilter};
+pub use group::ExprGroup;
+pub use if_::ExprIf;
+pub use index::ExprIndex;
+pub use match_::{Arm, ExprMatch};
+pub use message::ExprMessage;
+pub use method_call::ExprMethodCall;
+pub use paren::ExprParen;
+pub use range::ExprRange;
+pub use reference::ExprReference;
+pub use repeat::ExprRepeat;
+pub use struct_::ExprStruct;
+pub use tuple::ExprTuple;
+pub use type_::ExprType;
+pub use unary::ExprUnary;
+
 pub enum Expr {
     Array(ExprArray),
     Binary(ExprBinary),
+    Block(ExprBlock),
     Call(ExprCall),
     Cast(ExprCast),
+    Closure(ExprClosure),
     Field(ExprField),
+    Filter(ExprFilter),
     Group(ExprGroup),
+    If(ExprIf),
     Index(ExprIndex),
+    /// `let pat = expr` in the condition of an `if`
+    Let(Let),
+    Match(ExprMatch),
     MethodCall(ExprMethodCall),
+    /// Translated message, `t!("key", name = value)`
+    Message(ExprMessage),
     Paren(ExprParen),
     Path(ExprPath),
     Range(ExprRange),
@@ -70,6 +89,15 @@ pub enum Expr {
     // TODO:(view:expr) Only allow select macros?
     Macro(ExprMacro),
     Lit(Lit),
+    /// Metadata of the enclosing `!for` loop
+    Loop(Token![loop]),
+}
+
+impl Expr {
+    /// Whether the expression ends with a block, so it doesn't need a `;` or a `,` after it.
+    fn is_block_like(&self) -> bool {
+        matches!(self, Expr::Block(_) | Expr::If(_) | Expr::Match(_))
+    }
 }
 
 // The following code is copied and modified from syn
@@ -145,45 +173,6 @@ impl Parse for Expr {
     }
 }
 
-impl Tokenize for Expr {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        match self {
-            Expr::Array(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Binary(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Call(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Cast(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Field(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Group(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Index(e) => e.tokenize(tokens, idents, scopes),
-            Expr::MethodCall(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Paren(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Path(path) => {
-                if let Some(ident) = path.path.get_ident() {
-                    if !scopes.contains(&ident) && !KEYWORDS.contains(&ident.to_string().as_str()) {
-                        idents.push(ident.clone());
-                        tokens.append_all(quote! {
-                            self.#ident
-                        });
-                    } else {
-                        ident.to_tokens(tokens);
-                    }
-                } else {
-                    path.to_tokens(tokens);
-                }
-            }
-            Expr::Range(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Reference(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Repeat(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Struct(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Tuple(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Type(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Unary(e) => e.tokenize(tokens, idents, scopes),
-            Expr::Macro(e) => e.to_tokens(tokens),
-            Expr::Lit(e) => e.to_tokens(tokens),
-        };
-    }
-}
-
 pub(super) fn expr_no_struct(input: ParseStream) -> Result<Expr> {
     ambiguous_expr(input, AllowStruct(false))
 }
@@ -226,12 +215,7 @@ fn parse_expr(
     base: Precedence,
 ) -> Result<Expr> {
     loop {
-        if input
-            .fork()
-            .parse::<BinOp>()
-            .ok()
-            .map_or(false, |op| Precedence::of(&op) >= base)
-        {
+        if matches!(input.fork().parse::<BinOp>(), Ok(op) if Precedence::of(&op) >= base) {
             let op: BinOp = input.parse()?;
             let precedence = Precedence::of(&op);
 
@@ -301,10 +285,21 @@ fn parse_expr(
             let colon_token: Token![:] = input.parse()?;
             let ty = input.call(Type::without_plus)?;
 
+            // Default value of the field, `expr: type = default`
+            let default = if input.peek(Token![=])
+                && !input.peek(Token![==])
+                && !input.peek(Token![=>])
+            {
+                Some((input.parse()?, Box::new(ambiguous_expr(input, allow_struct)?)))
+            } else {
+                None
+            };
+
             lhs = Expr::Type(ExprType {
                 expr: Box::new(lhs),
                 colon_token,
                 ty: Box::new(ty),
+                default,
             });
         } else {
             break;
@@ -327,7 +322,7 @@ fn generic_method_argument(input: ParseStream) -> Result<GenericMethodArgument>
     input.parse().map(GenericMethodArgument::Type)
 }
 
-#[allow(clippy::eval_order_dependence)]
+#[allow(clippy::mixed_read_write_in_expression)]
 fn trailer_helper(input: ParseStream, mut e: Expr) -> Result<Expr> {
     loop {
         if input.peek(Paren) {
@@ -413,6 +408,18 @@ fn atom_expr(input: ParseStream, allow_struct: AllowStruct) -> Result<Expr> {
         Ok(Expr::Group(input.parse()?))
     } else if input.peek(Lit) {
         input.parse().map(Expr::Lit)
+    } else if input.peek(Token![loop]) {
+        input.parse().map(Expr::Loop)
+    } else if input.peek(Token![if]) {
+        input.parse().map(Expr::If)
+    } else if input.peek(Token![match]) {
+        input.parse().map(Expr::Match)
+    } else if input.peek(Token![let]) {
+        input.parse().map(Expr::Let)
+    } else if input.peek(Token![|]) || input.peek(Token![move]) {
+        input.parse().map(Expr::Closure)
+    } else if input.peek(Brace) {
+        input.parse().map(Expr::Block)
     } else if input.peek(Ident)
         || input.peek(Token![::])
         || input.peek(Token![<])
@@ -460,6 +467,15 @@ fn path_or_macro_or_struct(input: ParseStream, allow_struct: AllowStruct) -> Res
         return Ok(Expr::Path(expr));
     }
 
+    if expr.path.is_ident("t") && input.peek(Token![!]) && !input.peek(Token![!=]) {
+        let content;
+
+        input.parse::<Token![!]>()?;
+        parenthesized!(content in input);
+
+        return content.parse().map(Expr::Message);
+    }
+
     if input.peek(Token![!]) && !input.peek(Token![!=]) {
         let mut contains_arguments = false;
 
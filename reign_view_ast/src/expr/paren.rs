@@ -1,5 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     token::Paren,
@@ -28,11 +28,3 @@ impl Parse for ExprParen {
         }
     }
 }
-
-impl Tokenize for ExprParen {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.paren_token.surround(tokens, |tokens| {
-            self.expr.tokenize(tokens, idents, scopes);
-        });
-    }
-}
@@ -1,6 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     Error, RangeLimits,
@@ -24,16 +23,3 @@ impl Parse for ExprRange {
         }
     }
 }
-
-impl Tokenize for ExprRange {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.from.tokenize(tokens, idents, scopes);
-
-        match &self.limits {
-            RangeLimits::HalfOpen(t) => t.to_tokens(tokens),
-            RangeLimits::Closed(t) => t.to_tokens(tokens),
-        }
-
-        self.to.tokenize(tokens, idents, scopes);
-    }
-}
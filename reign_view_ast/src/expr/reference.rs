@@ -1,6 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     token::And,
@@ -29,10 +28,3 @@ impl Parse for ExprReference {
         }
     }
 }
-
-impl Tokenize for ExprReference {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.and_token.to_tokens(tokens);
-        self.expr.tokenize(tokens, idents, scopes);
-    }
-}
@@ -1,6 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     token::{Bracket, Semi},
@@ -31,13 +30,3 @@ impl Parse for ExprRepeat {
         }
     }
 }
-
-impl Tokenize for ExprRepeat {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.bracket_token.surround(tokens, |tokens| {
-            self.expr.tokenize(tokens, idents, scopes);
-            self.semi_token.to_tokens(tokens);
-            self.len.tokenize(tokens, idents, scopes);
-        });
-    }
-}
@@ -1,6 +1,5 @@
-use super::{Expr, FieldValue, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use super::{Expr, FieldValue};
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
@@ -33,22 +32,3 @@ impl Parse for ExprStruct {
         }
     }
 }
-
-impl Tokenize for ExprStruct {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.path.to_tokens(tokens);
-
-        self.brace_token.surround(tokens, |tokens| {
-            self.fields.tokenize(tokens, idents, scopes);
-
-            if self.rest.is_some() {
-                match self.dot2_token {
-                    Some(t) => t.to_tokens(tokens),
-                    None => Dot2::default().to_tokens(tokens),
-                }
-
-                self.rest.tokenize(tokens, idents, scopes);
-            }
-        })
-    }
-}
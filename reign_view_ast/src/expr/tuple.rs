@@ -0,0 +1,26 @@
+use super::Expr;
+use proc_macro2::Span;
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    punctuated::Punctuated,
+    token::{Comma, Paren},
+    Error,
+};
+
+pub struct ExprTuple {
+    pub paren_token: Paren,
+    pub elems: Punctuated<Expr, Comma>,
+}
+
+impl Parse for ExprTuple {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut expr: Expr = input.parse()?;
+        loop {
+            match expr {
+                Expr::Tuple(inner) => return Ok(inner),
+                Expr::Group(next) => expr = *next.expr,
+                _ => return Err(Error::new(Span::call_site(), "expected tuple expression")),
+            }
+        }
+    }
+}
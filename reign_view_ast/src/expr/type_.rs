@@ -0,0 +1,33 @@
+use super::Expr;
+use proc_macro2::Span;
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    token::{Colon, Eq},
+    Error, Type,
+};
+
+pub struct ExprType {
+    pub expr: Box<Expr>,
+    #[allow(dead_code)]
+    pub colon_token: Colon,
+    pub ty: Box<Type>,
+    pub default: Option<(Eq, Box<Expr>)>,
+}
+
+impl Parse for ExprType {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut expr: Expr = input.parse()?;
+        loop {
+            match expr {
+                Expr::Type(inner) => return Ok(inner),
+                Expr::Group(next) => expr = *next.expr,
+                _ => {
+                    return Err(Error::new(
+                        Span::call_site(),
+                        "expected type ascription expression",
+                    ))
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,5 @@
-use super::{Expr, Tokenize, ViewFields};
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use super::Expr;
+use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream, Result},
     Error, UnOp,
@@ -23,10 +22,3 @@ impl Parse for ExprUnary {
         }
     }
 }
-
-impl Tokenize for ExprUnary {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.op.to_tokens(tokens);
-        self.expr.tokenize(tokens, idents, scopes);
-    }
-}
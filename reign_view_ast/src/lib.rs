@@ -0,0 +1,384 @@
+#![doc(html_logo_url = "https://reign.rs/images/media/reign.png")]
+#![doc(html_root_url = "https://docs.rs/reign_view_ast/0.2.1")]
+#![doc = include_str!("../README.md")]
+
+pub mod minify;
+pub mod parse;
+
+// Parsing and tokenizing for the rust language, we don't really need to
+// "understand" this code in the same way, so I haven't spent much time refactoring
+// it. Would love it if we could shell out this part to libraries more.
+pub mod expr;
+pub mod pat;
+
+use std::fmt::{Debug, Formatter, Error as FError};
+
+use inflector::cases::{pascalcase::to_pascal_case, snakecase::to_snake_case};
+use proc_macro2::Span;
+use syn::{Ident, Member};
+
+use self::{expr::Expr, pat::{Case, For, Let}};
+
+#[derive(Debug)]
+pub struct ItemTemplate {
+    pub name: String,
+    pub attrs: Vec<Attribute>,
+    pub children: Vec<Node>,
+    /// Views built from the `<template #name>` elements in the same file.
+    pub named: Vec<ItemTemplate>,
+    /// Style node attatched to this template node,
+    /// doesn't include style from included elements.
+    pub style: String,
+    /// Contents of the `<script>` elements in the file.
+    pub script: String,
+    /// Contents of the `<docs>` elements in the file.
+    pub docs: String,
+    /// Attribute added to every element when the file has a `<style scoped>`.
+    pub scope: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Node {
+    Element(Element),
+    Comment(Comment),
+    Text(Text),
+    Doctype(Doctype),
+}
+
+#[derive(Debug)]
+pub struct Element {
+    pub name: String,
+    pub attrs: Vec<Attribute>,
+    pub children: Vec<Node>,
+    /// Byte offset of the element in the template file
+    pub pos: usize,
+}
+
+#[derive(Debug)]
+pub struct Text {
+    pub content: Vec<StringPart>,
+    /// Byte offset of the text in the template file
+    pub pos: usize,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum Attribute {
+    Normal(NormalAttribute),
+    Dynamic(DynamicAttribute),
+    Variable(VariableAttribute),
+    Control(ControlAttribute),
+}
+
+#[derive(Debug)]
+pub struct NormalAttribute {
+    pub name: String,
+    pub value: AttributeValue,
+}
+
+#[derive(Debug)]
+pub struct AttributeValue {
+    pub parts: Vec<StringPart>,
+}
+
+#[derive(Debug)]
+pub struct DynamicAttribute {
+    #[allow(dead_code)]
+    pub symbol: String,
+    pub prefix: String,
+    pub name: Code,
+    pub suffix: String,
+    pub value: Code,
+}
+
+#[derive(Debug)]
+pub struct VariableAttribute {
+    pub name: String,
+    pub value: Code,
+}
+
+#[derive(Debug)]
+pub struct ControlAttribute {
+    pub name: String,
+    pub value: Code,
+}
+
+#[derive(Debug)]
+pub struct Doctype {
+    pub content: String,
+}
+
+#[derive(Debug)]
+pub struct Comment {
+    pub content: String,
+}
+
+pub enum Code {
+    For(For),
+    Case(Case),
+    Let(Let),
+    Expr(Expr),
+}
+
+#[derive(Debug)]
+pub enum StringPart {
+    Normal(String),
+    Expr(Code),
+}
+
+impl Debug for Code {
+    fn fmt(&self, _: &mut Formatter) -> Result<(), FError> {
+        Ok(())
+    }
+}
+
+impl Node {
+    /// Whether the node is text made only of whitespace.
+    pub fn is_blank(&self) -> bool {
+        match self {
+            Node::Text(t) => t.content.iter().all(|part| match part {
+                StringPart::Normal(s) => s.trim().is_empty(),
+                StringPart::Expr(_) => false,
+            }),
+            _ => false,
+        }
+    }
+}
+
+impl Element {
+    pub fn control_attr(&self, name: &str) -> Option<&ControlAttribute> {
+        for attr in &self.attrs {
+            if let Attribute::Control(control) = attr {
+                if control.name == name {
+                    return Some(control);
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn variable_attr(&self, name: &str) -> Option<&VariableAttribute> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attribute::Variable(v) if v.name == name => Some(v),
+            _ => None,
+        })
+    }
+
+    pub fn has_attr(&self, name: &str) -> bool {
+        self.attrs.iter().any(|attr| match attr {
+            Attribute::Normal(n) => n.name == name,
+            Attribute::Variable(v) => v.name == name,
+            _ => false,
+        })
+    }
+
+    /// Whether the fields of the component which are not given are
+    /// taken from another value with `:..="props"`.
+    pub fn has_spread(&self) -> bool {
+        self.variable_attr("..").is_some()
+    }
+
+    /// Whether the attributes given to the component are forwarded to this element.
+    pub fn inherits_attrs(&self) -> bool {
+        self.control_attr("inherit-attrs").is_some()
+    }
+
+    /// Path of the view rendered by this element if it is a component.
+    pub fn component_path(&self) -> Option<Vec<Ident>> {
+        let tag_pieces: Vec<&str> = self.name.split(':').collect();
+
+        if self.name == "template"
+            || self.name == "slot"
+            || tag_pieces.len() == 1 && is_reserved_tag(&self.name)
+        {
+            None
+        } else {
+            Some(convert_tag_name(tag_pieces))
+        }
+    }
+
+    /// Names of the fields given to this element if it is a component.
+    pub fn component_props(&self) -> Vec<Ident> {
+        self.attrs
+            .iter()
+            .filter_map(|attr| match attr {
+                Attribute::Normal(n) => Some(&n.name),
+                Attribute::Variable(v) if v.name != ".." => Some(&v.name),
+                _ => None,
+            })
+            .map(|name| Ident::new(&to_snake_case(name), Span::call_site()))
+            .collect()
+    }
+
+    pub fn template_name(&self) -> Option<String> {
+        if self.name == "template" {
+            for attr in &self.attrs {
+                if let Attribute::Normal(n) = attr {
+                    if n.name.starts_with('#') {
+                        return Some(n.name.clone());
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn slot_name(&self) -> String {
+        for attr in &self.attrs {
+            if let Attribute::Normal(n) = attr {
+                if n.name == "name" {
+                    if let Some(value) = n.value.value() {
+                        return value;
+                    }
+                }
+            }
+        }
+
+        "default".to_string()
+    }
+}
+
+impl AttributeValue {
+    pub fn value(&self) -> Option<String> {
+        let mut strings: Vec<String> = vec![];
+
+        for part in &self.parts {
+            if let StringPart::Normal(s) = part {
+                strings.push(s.clone());
+            } else {
+                return None;
+            }
+        }
+
+        Some(strings.join(""))
+    }
+
+    pub fn has_expr(&self) -> bool {
+        for part in &self.parts {
+            if let StringPart::Expr(_) = part {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Whether the interpolations can change the scheme of the value used as
+    /// a URL, like in `{{ scheme }}:{{ rest }}` or `java{{ x }}`, in which case
+    /// the scheme can only be checked once the whole value is built.
+    pub fn builds_scheme(&self) -> bool {
+        let mut has_expr = false;
+        let mut static_start = false;
+
+        for part in &self.parts {
+            match part {
+                StringPart::Expr(_) => has_expr = true,
+                StringPart::Normal(s) => {
+                    let end = s.find(|c| matches!(c, ':' | '/' | '?' | '#'));
+                    let prefix = &s[..end.unwrap_or(s.len())];
+
+                    // Browsers ignore the leading spaces of URLs
+                    if !has_expr && prefix.chars().any(|c| c > ' ') {
+                        static_start = true;
+                    }
+
+                    if let Some(end) = end {
+                        return has_expr && (static_start || s[end..].starts_with(':'));
+                    }
+                }
+            }
+        }
+
+        has_expr && static_start
+    }
+}
+
+/// Path of the view rendered by a component tag like `shared:user-card`.
+pub fn convert_tag_name(tag: Vec<&str>) -> Vec<Ident> {
+    let mut idents: Vec<Ident> = tag
+        .into_iter()
+        .map(|t| Ident::new(&to_snake_case(t), Span::call_site()))
+        .collect();
+
+    if let Some(ident) = idents.pop() {
+        let new_ident = to_pascal_case(&ident.to_string());
+        idents.push(Ident::new(&new_ident, Span::call_site()));
+    }
+
+    idents
+}
+
+/// Decodes the numeric character references of the text, with or without
+/// their `;`, and the named ones in `parse::consts::NAMED_REFERENCES`.
+pub fn decode_references(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        match reference(rest) {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => out.push('&'),
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+// Character of the reference at the start of `rest`, which follows a `&`,
+// along with the length of the reference
+fn reference(rest: &str) -> Option<(char, usize)> {
+    use parse::consts::NAMED_REFERENCES;
+
+    let number = match rest.strip_prefix('#') {
+        Some(number) => number,
+        None => {
+            return NAMED_REFERENCES
+                .iter()
+                .find(|(name, _)| rest.starts_with(name) && rest[name.len()..].starts_with(';'))
+                .map(|(name, c)| (*c, name.len() + 1));
+        }
+    };
+    let (digits, radix, prefix) = match number.strip_prefix(|c| c == 'x' || c == 'X') {
+        Some(hex) => (hex, 16, 2),
+        None => (number, 10, 1),
+    };
+    let len = digits.find(|c: char| !c.is_digit(radix)).unwrap_or(digits.len());
+
+    if len == 0 {
+        return None;
+    }
+
+    let c = u32::from_str_radix(&digits[..len], radix)
+        .ok()
+        .filter(|code| *code != 0)
+        .and_then(char::from_u32)
+        .unwrap_or('\u{FFFD}');
+    let semicolon = if digits[len..].starts_with(';') { 1 } else { 0 };
+
+    Some((c, prefix + len + semicolon))
+}
+
+/// Whether the tag is an html or svg element instead of a component.
+pub fn is_reserved_tag(tag: &str) -> bool {
+    use parse::consts::{HTML_TAGS, SVG_TAGS};
+
+    SVG_TAGS.contains(&tag) || HTML_TAGS.contains(&tag)
+}
+
+// Utils
+
+fn is_member_named(member: &Member) -> bool {
+    match member {
+        Member::Named(_) => true,
+        Member::Unnamed(_) => false,
+    }
+}
@@ -0,0 +1,194 @@
+use super::{Comment, ItemTemplate, Node, StringPart};
+
+/// Elements whose text is written as it is.
+const PRESERVED: &[&str] = &["pre", "textarea", "script", "style"];
+
+/// Elements where whitespace between the children is never rendered.
+const BLOCKS: &[&str] = &["html", "head", "table", "thead", "tbody", "tfoot", "tr", "colgroup", "select"];
+
+/// Collapses the runs of whitespace in the text of the template and its
+/// named templates to a single space, except inside `<pre>`, `<textarea>`,
+/// `<script>` and `<style>`, and drops the comments which are not
+/// conditional comments.
+pub fn minify(template: &mut ItemTemplate) {
+    nodes(&mut template.children, false);
+
+    if let Some(Node::Text(text)) = template.children.first_mut() {
+        if let Some(StringPart::Normal(first)) = text.content.first_mut() {
+            *first = first.trim_start().to_string();
+        }
+    }
+
+    if let Some(Node::Text(text)) = template.children.last_mut() {
+        if let Some(StringPart::Normal(last)) = text.content.last_mut() {
+            *last = last.trim_end().to_string();
+        }
+    }
+
+    template.children.retain(|node| !is_empty(node));
+
+    for named in &mut template.named {
+        minify(named);
+    }
+}
+
+fn nodes(nodes: &mut Vec<Node>, block: bool) {
+    nodes.retain(|node| match node {
+        Node::Comment(comment) => is_conditional(comment),
+        Node::Text(text) if block => !text.content.iter().all(|part| match part {
+            StringPart::Normal(text) => text.trim().is_empty(),
+            StringPart::Expr(_) => false,
+        }),
+        _ => true,
+    });
+
+    merge_texts(nodes);
+
+    for node in nodes.iter_mut() {
+        match node {
+            Node::Text(text) => {
+                for part in &mut text.content {
+                    if let StringPart::Normal(text) = part {
+                        *text = collapse(text);
+                    }
+                }
+            }
+            Node::Element(element) if !PRESERVED.contains(&element.name.as_str()) => {
+                let block = BLOCKS.contains(&element.name.as_str());
+
+                self::nodes(&mut element.children, block);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Joins the texts which were around the dropped comments.
+fn merge_texts(nodes: &mut Vec<Node>) {
+    let mut merged: Vec<Node> = Vec::with_capacity(nodes.len());
+
+    for node in nodes.drain(..) {
+        match (merged.last_mut(), node) {
+            (Some(Node::Text(last)), Node::Text(text)) => {
+                for part in text.content {
+                    match (last.content.last_mut(), part) {
+                        (Some(StringPart::Normal(last)), StringPart::Normal(text)) => last.push_str(&text),
+                        (_, part) => last.content.push(part),
+                    }
+                }
+            }
+            (_, node) => merged.push(node),
+        }
+    }
+
+    *nodes = merged;
+}
+
+/// Conditional comments like `<!--[if IE]>...<![endif]-->` are read by old browsers.
+fn is_conditional(comment: &Comment) -> bool {
+    comment.content.starts_with("[if") || comment.content.starts_with("<![endif]")
+}
+
+fn is_empty(node: &Node) -> bool {
+    match node {
+        Node::Text(text) => text.content.iter().all(|part| match part {
+            StringPart::Normal(text) => text.is_empty(),
+            StringPart::Expr(_) => false,
+        }),
+        _ => false,
+    }
+}
+
+fn collapse(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut space = false;
+
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            space = true;
+            continue;
+        }
+
+        if space {
+            out.push(' ');
+            space = false;
+        }
+
+        out.push(c);
+    }
+
+    if space {
+        out.push(' ');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::parse::parse, minify};
+    use crate::{Node, StringPart};
+
+    fn text(node: &Node) -> String {
+        match node {
+            Node::Text(text) => text
+                .content
+                .iter()
+                .map(|part| match part {
+                    StringPart::Normal(text) => text.clone(),
+                    StringPart::Expr(_) => "{}".to_string(),
+                })
+                .collect(),
+            node => panic!("expected text, found {:?}", node),
+        }
+    }
+
+    #[test]
+    fn test_minify() {
+        let mut item = parse(
+            "<template>\n  <p>\n    Hello,\n\t{{ name }}  !\n  </p>\n  <!-- note -->\n  <!--[if IE]><p>IE</p><![endif]-->\n  <pre>  a\n  b</pre>\n  <table>\n    <tr> <td> x </td> </tr>\n  </table>\n</template>"
+                .to_string(),
+            "Card".to_string(),
+            "",
+        )
+        .unwrap();
+
+        minify(&mut item);
+
+        let nodes = &item.children;
+
+        assert_eq!(nodes.len(), 7);
+
+        match &nodes[0] {
+            Node::Element(p) => assert_eq!(text(&p.children[0]), " Hello, {} ! "),
+            node => panic!("expected `p`, found {:?}", node),
+        }
+
+        assert_eq!(text(&nodes[1]), " ");
+        assert!(matches!(&nodes[2], Node::Comment(comment) if comment.content == "[if IE]><p>IE</p><![endif]"));
+
+        match &nodes[4] {
+            Node::Element(pre) => assert_eq!(text(&pre.children[0]), "  a\n  b"),
+            node => panic!("expected `pre`, found {:?}", node),
+        }
+
+        match &nodes[6] {
+            Node::Element(table) => {
+                assert_eq!(table.children.len(), 1);
+
+                match &table.children[0] {
+                    Node::Element(tr) => {
+                        assert_eq!(tr.children.len(), 1);
+
+                        match &tr.children[0] {
+                            Node::Element(td) => assert_eq!(text(&td.children[0]), " x "),
+                            node => panic!("expected `td`, found {:?}", node),
+                        }
+                    }
+                    node => panic!("expected `tr`, found {:?}", node),
+                }
+            }
+            node => panic!("expected `table`, found {:?}", node),
+        }
+    }
+}
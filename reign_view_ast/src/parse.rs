@@ -0,0 +1,847 @@
+use super::*;
+
+use inflector::cases::pascalcase::to_pascal_case;
+
+mod parse_stream;
+mod error;
+mod style;
+pub mod consts;
+
+use consts::*;
+pub use error::Error;
+use parse_stream::ParseStream;
+use style::{parse_style, scope_attribute};
+use regex::Regex;
+use syn::{parse::Parser, parse_str};
+
+/// Parses a template file, `module` being the path of its module inside the
+/// views, like `shared` for `views/shared/button.html`.
+pub fn parse(data: String, template_name: String, module: &str) -> Result<ItemTemplate, Error> {
+    let mut ps = ParseStream::new(data);
+    ItemTemplate::parse(&mut ps, template_name, module)
+}
+
+trait Parse: Sized {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error>;
+}
+
+impl ItemTemplate {
+    fn parse(input: &mut ParseStream, name: String, module: &str) -> Result<Self, Error> {
+        let mut template = None;
+        let mut named = vec![];
+        let mut styles = vec![];
+        let mut script = String::new();
+        let mut docs = String::new();
+
+        loop {
+            input.skip_spaces()?;
+
+            if input.cursor == input.content.len() {
+                break;
+            }
+
+            if input.peek("<!--") {
+                input.parse::<Comment>()?;
+                continue;
+            }
+
+            let pos = input.cursor;
+            let tag_name = match input.capture(&tag_name_regex(), 1) {
+                Ok(tag) => tag,
+                Err(_) => return Err(input.error("expected a top-level element")),
+            };
+
+            match tag_name.as_str() {
+                "template" => {
+                    let attrs = parse_element_attrs(input)?;
+                    let children = parse_element_children(input, "template")?;
+
+                    if let Some(block) = block_name(&attrs) {
+                        named.push(ItemTemplate {
+                            name: format!("{}{}", name, to_pascal_case(&block)),
+                            attrs,
+                            children,
+                            named: vec![],
+                            style: String::new(),
+                            script: String::new(),
+                            docs: String::new(),
+                            scope: None,
+                        });
+                    } else if template.is_some() {
+                        return Err(input.error("expected a single unnamed 'template' element"));
+                    } else {
+                        template = Some(with_layout(input, pos, attrs, children)?);
+                    }
+                }
+                "style" => styles.push(parse_style(input)?),
+                "script" => {
+                    let content = parse_raw_element(input, "script", &["type"])?;
+
+                    // Keeps a missing `;` or a trailing line comment from running into the next script
+                    if !script.is_empty() {
+                        script.push_str(SCRIPT_SEPARATOR);
+                    }
+
+                    script.push_str(&content);
+                }
+                "docs" => docs.push_str(&parse_raw_element(input, "docs", &[])?),
+                _ => {
+                    return Err(input.error(
+                        "expected 'template', 'style', 'script' or 'docs' element, found something else",
+                    ))
+                }
+            }
+        }
+
+        let (attrs, children) = template.ok_or_else(|| input.error("Missing 'template' element"))?;
+        let scope = scope_attribute(module, &name, &styles);
+        let style = styles.iter().map(|style| style.to_css(scope.as_deref())).collect();
+
+        for template in &mut named {
+            template.scope = scope.clone();
+        }
+
+        Ok(ItemTemplate {
+            name,
+            attrs,
+            children,
+            named,
+            style,
+            script,
+            docs,
+            scope,
+        })
+    }
+}
+
+// A template with `layout="layouts:app"` is rendered as the children of the
+// `<layouts:app>` component, so that its `<template #name>` blocks fill the
+// slots of the layout. The other attributes of the template are its props.
+fn with_layout(
+    input: &ParseStream,
+    pos: usize,
+    mut attrs: Vec<Attribute>,
+    children: Vec<Node>,
+) -> Result<(Vec<Attribute>, Vec<Node>), Error> {
+    let index = attrs
+        .iter()
+        .position(|attr| matches!(attr, Attribute::Normal(n) if n.name == "layout"));
+
+    let layout = match index.map(|i| attrs.remove(i)) {
+        Some(Attribute::Normal(n)) => n.value.value(),
+        _ => return Ok((attrs, children)),
+    };
+
+    let element = Element {
+        name: layout.unwrap_or_default(),
+        attrs,
+        children,
+        pos,
+    };
+
+    if element.component_path().is_none() || element.name.is_empty() {
+        let mut err = input.error("expected the path of a component in `layout`, like `layouts:app`");
+
+        err.cursor = pos;
+        return Err(err);
+    }
+
+    Ok((vec![], vec![Node::Element(element)]))
+}
+
+// Name of a `<template #name>` element from its attributes
+fn block_name(attrs: &[Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match attr {
+        Attribute::Normal(n) if n.name.starts_with('#') => {
+            Some(n.name.trim_start_matches('#').to_string())
+        }
+        _ => None,
+    })
+}
+
+/// Written between the scripts when they are joined together.
+pub const SCRIPT_SEPARATOR: &str = "\n;\n";
+
+// Grabs the contents of elements whose contents we don't need to understand,
+// ignoring the `allowed` attributes
+fn parse_raw_element(input: &mut ParseStream, tag_name: &str, allowed: &[&str]) -> Result<String, Error> {
+    let attrs = parse_element_attrs(input)?;
+    let unsupported = attrs
+        .iter()
+        .any(|attr| !matches!(attr, Attribute::Normal(n) if allowed.contains(&n.name.as_str())));
+
+    if unsupported {
+        return Err(input.error(&match allowed {
+            [] => format!("Attributes on '{}' elements unsupported", tag_name),
+            _ => format!("Only the `{}` attributes are supported on '{}' elements", allowed.join("`, `"), tag_name),
+        }));
+    }
+
+    if input.peek("/>") {
+        return Err(input.error(&format!("Self closing {} tags unsupported (sorry)", tag_name)))
+    }
+
+    input.step(">")?;
+
+    // TODO: Robust tag parsing (things like </ style>)
+    let out = input.until(&format!("</{}>", tag_name), true)?;
+    Ok(out)
+}
+
+fn parse_element_attrs(input: &mut ParseStream) -> Result<Vec<Attribute>, Error> {
+    let mut attrs = vec![];
+    input.skip_spaces()?;
+
+    while !input.peek("/>") && !input.peek(">") {
+        attrs.push(input.parse()?);
+        input.skip_spaces()?;
+    }
+
+    Ok(attrs)
+}
+
+fn parse_element_children(input: &mut ParseStream, tag_name: &str) -> Result<Vec<Node>, Error> {
+    let mut children = vec![];
+
+    if input.peek("/>") {
+        input.step("/>")?;
+    } else {
+        // input.peek(">") is true here
+        input.step(">")?;
+
+        // TODO:(view:html) Tags that can be left open according to HTML spec
+        if !VOID_TAGS.contains(&tag_name) {
+            let closing_tag = format!("</{}", tag_name);
+
+            while !input.peek(&closing_tag) {
+                let child = input.parse()?;
+                children.push(child);
+            }
+
+            input.step(&closing_tag)?;
+            input.skip_spaces()?;
+            input.step(">")?;
+        }
+    }
+
+    Ok(children)
+}
+
+impl Parse for Node {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        if input.cursor == 0 {
+            input.skip_spaces()?;
+        }
+
+        if input.peek("<!--") {
+            Ok(Node::Comment(input.parse()?))
+        } else if input.is_match(DOCTYPE) {
+            Ok(Node::Doctype(input.parse()?))
+        } else if input.is_match(&tag_name_regex()) {
+            Ok(Node::Element(input.parse()?))
+        } else {
+            let text: Text = input.parse()?;
+
+            // Text made of template comments only is empty
+            if text.content.is_empty() && input.cursor == text.pos {
+                return Err(input.error("unable to continue parsing"));
+            }
+
+            Ok(Node::Text(text))
+        }
+    }
+}
+
+fn tag_name_regex() -> String {
+    format!("<({0}(:?:{0})*)", consts::TAG_NAME)
+}
+
+impl Parse for Element {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        let pos = input.cursor;
+        let name = input.capture(&tag_name_regex(), 1)?;
+
+        Ok(Element {
+            name: name.to_lowercase(),
+            attrs: parse_element_attrs(input)?,
+            children: parse_element_children(input, &name)?,
+            pos,
+        })
+    }
+}
+
+impl Parse for Text {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        let pos = input.cursor;
+
+        Ok(Text {
+            content: input.parse_text()?,
+            pos,
+        })
+    }
+}
+
+impl Parse for Attribute {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        if input.is_match(&dy_attr_regex()) {
+            Ok(Attribute::Dynamic(input.parse()?))
+        } else if input.is_match(&var_attr_regex()) {
+            Ok(Attribute::Variable(input.parse()?))
+        } else if input.is_match(CTRL_ATTR) {
+            Ok(Attribute::Control(input.parse()?))
+        } else if input.is_match(ATTR_NAME) {
+            Ok(Attribute::Normal(input.parse()?))
+        } else {
+            Err(input.error("unable to parse attribute"))
+        }
+    }
+}
+
+fn dy_attr_regex() -> String {
+    format!(
+        "{}{2}{}{2}",
+        VAR_ATTR_SYMBOL, DY_ATTR_EXPR, DY_ATTR_NAME_PART
+    )
+}
+
+fn var_attr_regex() -> String {
+    format!("{}({})", VAR_ATTR_SYMBOL, ATTR_NAME)
+}
+
+impl Parse for NormalAttribute {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        Ok(NormalAttribute {
+            name: input.matched(ATTR_NAME)?,
+            value: input.parse()?,
+        })
+    }
+}
+
+impl Parse for AttributeValue {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        Ok(AttributeValue {
+            parts: {
+                let value = AttributeValue::parse_to_str(input)?;
+                StringPart::parse(input, &value, true)?
+            },
+        })
+    }
+}
+
+impl AttributeValue {
+    pub fn parse_to_str(input: &mut ParseStream) -> Result<String, Error> {
+        input.skip_spaces()?;
+
+        if input.peek("=") {
+            input.step("=")?;
+            input.skip_spaces()?;
+
+            if input.peek("\"") {
+                Ok(input.capture(ATTR_VALUE_DOUBLE_QUOTED, 1)?)
+            } else if input.peek("\'") {
+                Ok(input.capture(ATTR_VALUE_SINGLE_QUOTED, 1)?)
+            } else {
+                Ok(input.matched(ATTR_VALUE_UNQUOTED)?)
+            }
+        } else {
+            Ok("\"\"".to_string())
+        }
+    }
+}
+
+impl Parse for DynamicAttribute {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        Ok(DynamicAttribute {
+            symbol: input.step(":")?,
+            prefix: input.matched(DY_ATTR_NAME_PART)?,
+            name: {
+                let name = input.capture(DY_ATTR_EXPR, 1)?;
+                Code::parse_expr_from_str(input, &name)?
+            },
+            suffix: input.matched(DY_ATTR_NAME_PART)?,
+            value: Code::parse_expr(input)?,
+        })
+    }
+}
+
+impl Parse for VariableAttribute {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        Ok(VariableAttribute {
+            name: input.capture(&var_attr_regex(), 1)?,
+            value: Code::parse_expr(input)?,
+        })
+    }
+}
+
+impl Parse for ControlAttribute {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        let name = input.capture(CTRL_ATTR, 1)?;
+
+        Ok(ControlAttribute {
+            name: name.clone(),
+            value: {
+                if name == "for" {
+                    Code::parse_for(input)?
+                } else if name == "case" {
+                    Code::parse_case(input)?
+                } else if name == "if" || name == "else-if" {
+                    Code::parse_condition(input)?
+                } else {
+                    Code::parse_expr(input)?
+                }
+            },
+        })
+    }
+}
+
+impl Parse for Doctype {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        Ok(Doctype {
+            content: input.matched(DOCTYPE)?,
+        })
+    }
+}
+
+impl Parse for Comment {
+    fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        input.step("<!--")?;
+
+        Ok(Comment {
+            content: input.until("-->", true)?,
+        })
+    }
+}
+
+impl Code {
+    pub fn parse_for(input: &mut ParseStream) -> Result<Self, Error> {
+        let string = AttributeValue::parse_to_str(input)?;
+        Self::parse_for_from_str(input, &string)
+    }
+
+    pub fn parse_case(input: &mut ParseStream) -> Result<Self, Error> {
+        let string = AttributeValue::parse_to_str(input)?;
+
+        match parse_str::<Case>(&string) {
+            Ok(case) => Ok(Code::Case(case)),
+            Err(_) => Err(input.error("expected pattern in `!case`")),
+        }
+    }
+
+    /// Parses either an expression or `let pattern = expression`.
+    pub fn parse_condition(input: &mut ParseStream) -> Result<Self, Error> {
+        let string = AttributeValue::parse_to_str(input)?;
+
+        if string.split_whitespace().next() != Some("let") {
+            return Self::parse_expr_from_str(input, &string);
+        }
+
+        match parse_str::<Let>(&string) {
+            Ok(let_) => Ok(Code::Let(let_)),
+            Err(_) => Err(input.error("expected `let pattern = expression`")),
+        }
+    }
+
+    pub fn parse_expr(input: &mut ParseStream) -> Result<Self, Error> {
+        let string = AttributeValue::parse_to_str(input)?;
+        Self::parse_expr_from_str(input, &string)
+    }
+
+    pub fn parse_for_from_str(input: &mut ParseStream, text: &str) -> Result<Self, Error> {
+        let parsed = parse_str::<For>(text);
+
+        if let Ok(code) = parsed {
+            Ok(Code::For(code))
+        } else {
+            Err(input.error("expected pattern in expression"))
+        }
+    }
+
+    pub fn parse_expr_from_str(input: &ParseStream, text: &str) -> Result<Self, Error> {
+        let parsed = parse_str::<Expr>(text);
+
+        if let Ok(code) = parsed {
+            Ok(Code::Expr(code))
+        } else {
+            Err(input.error("expected expression"))
+        }
+    }
+
+    /// Parses the expression of an interpolation, which can be followed by filters.
+    pub fn parse_filtered_from_str(input: &ParseStream, text: &str) -> Result<Self, Error> {
+        match expr::filtered_expr.parse_str(text) {
+            Ok(code) => Ok(Code::Expr(code)),
+            Err(_) => Err(input.error("expected expression")),
+        }
+    }
+}
+
+impl StringPart {
+    pub fn parse(input: &mut ParseStream, data: &str, in_attr: bool) -> Result<Vec<Self>, Error> {
+        let mut parts = vec![];
+        let start_regex = Regex::new(r"\\\{\{|\{\{|<").unwrap();
+        let mut cursor = if !in_attr { input.cursor } else { 0 };
+        let mut trim_start = false;
+
+        loop {
+            if trim_start {
+                let remaining = data.get(cursor..).unwrap();
+
+                cursor += remaining.len() - remaining.trim_start().len();
+                trim_start = false;
+            }
+
+            let remaining = data.get(cursor..).unwrap();
+
+            if remaining.is_empty() {
+                break;
+            }
+
+            let matches = start_regex.find(remaining);
+
+            if matches.is_none() {
+                parts.push(StringPart::Normal(remaining.to_string()));
+                cursor += remaining.len();
+                break;
+            }
+
+            let until = cursor + matches.unwrap().start();
+            let sub_string = data.get(cursor..until).unwrap();
+
+            if !sub_string.is_empty() {
+                parts.push(StringPart::Normal(sub_string.to_string()));
+                cursor = until;
+            }
+
+            match data.get(cursor..=cursor).unwrap() {
+                "\\" => {
+                    parts.push(StringPart::Normal("\\{{".to_string()));
+                    cursor += 3;
+                }
+                "<" => {
+                    if in_attr {
+                        parts.push(StringPart::Normal("<".to_string()));
+                        cursor += 1;
+                    } else {
+                        break;
+                    }
+                }
+                "{" if data[cursor..].starts_with("{{#") => {
+                    // Template comments are left out of the views
+                    let end_matches = data.get(cursor + 3..).unwrap().find("#}}");
+
+                    match end_matches {
+                        Some(end) => cursor += end + 6,
+                        None => {
+                            if !in_attr {
+                                input.cursor = cursor;
+                            }
+
+                            return Err(input.error("comment incomplete"));
+                        }
+                    }
+                }
+                "{" => {
+                    cursor += 2;
+                    let end_remaining = data.get(cursor..).unwrap();
+                    let end_matches = end_remaining.find("}}");
+
+                    if end_matches.is_none() {
+                        if !in_attr {
+                            input.cursor = cursor;
+                        }
+
+                        return Err(input.error("expression incomplete"));
+                    }
+
+                    let expr_until = cursor + end_matches.unwrap();
+                    let mut expr_string = data.get(cursor..expr_until).unwrap();
+
+                    // `{{-` and `-}}` remove the whitespace before and after
+                    if let Some(rest) = expr_string.strip_prefix('-') {
+                        expr_string = rest;
+
+                        if let Some(StringPart::Normal(text)) = parts.last_mut() {
+                            text.truncate(text.trim_end().len());
+
+                            if text.is_empty() {
+                                parts.pop();
+                            }
+                        }
+                    }
+
+                    if let Some(rest) = expr_string.strip_suffix('-') {
+                        expr_string = rest;
+                        trim_start = true;
+                    }
+
+                    parts.push(StringPart::Expr(Code::parse_filtered_from_str(
+                        input,
+                        expr_string,
+                    )?));
+                    cursor = expr_until + 2;
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if !in_attr {
+            input.cursor = cursor;
+        }
+
+        Ok(parts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, Attribute, Code, Expr, Node, StringPart};
+
+    #[test]
+    fn test_parse_blocks() {
+        let item = parse(
+            "<!-- comment -->
+<docs>Docs</docs>
+<template><p></p><p></p></template>
+<template #compact><b></b></template>
+<style>p {}</style>
+<script>run();</script>
+<script type=\"module\">// Done
+</script>
+"
+            .to_string(),
+            "Card".to_string(),
+            "",
+        )
+        .unwrap();
+
+        assert_eq!(item.children.len(), 2);
+        assert_eq!(item.named.len(), 1);
+        assert_eq!(item.named[0].name, "CardCompact");
+        assert_eq!(item.style, "p {}\n");
+        assert_eq!(item.scope, None);
+        assert_eq!(item.script, "run();\n;\n// Done\n");
+        assert_eq!(item.docs, "Docs");
+    }
+
+    #[test]
+    fn test_parse_script_attributes() {
+        let err = parse("<template></template><script src=\"a.js\"></script>".to_string(), "Card".to_string(), "")
+            .unwrap_err();
+
+        assert_eq!(err.message, "Only the `type` attributes are supported on 'script' elements");
+    }
+
+    #[test]
+    fn test_parse_multiple_unnamed_templates() {
+        let err = parse(
+            "<template></template><template></template>".to_string(),
+            "Card".to_string(),
+            "",
+        )
+        .unwrap_err();
+
+        assert_eq!(err.message, "expected a single unnamed 'template' element");
+    }
+
+    #[test]
+    fn test_parse_unknown_block() {
+        let err = parse("<template></template><div></div>".to_string(), "Card".to_string(), "")
+            .unwrap_err();
+
+        assert_eq!(
+            err.message,
+            "expected 'template', 'style', 'script' or 'docs' element, found something else"
+        );
+    }
+
+    #[test]
+    fn test_parse_layout() {
+        let item = parse(
+            "<template layout=\"layouts:app\" :user=\"user\"><template #title>Home</template><p></p></template>"
+                .to_string(),
+            "Home".to_string(),
+            "",
+        )
+        .unwrap();
+
+        assert!(item.attrs.is_empty());
+        assert_eq!(item.children.len(), 1);
+
+        match &item.children[0] {
+            Node::Element(e) => {
+                assert_eq!(e.name, "layouts:app");
+                assert_eq!(e.attrs.len(), 1);
+                assert_eq!(e.children.len(), 2);
+            }
+            node => panic!("expected the layout element, found {:?}", node),
+        }
+
+        let err = parse("<template layout=\"div\"></template>".to_string(), "Home".to_string(), "")
+            .unwrap_err();
+
+        assert_eq!(err.message, "expected the path of a component in `layout`, like `layouts:app`");
+        assert_eq!(err.cursor, 0);
+    }
+
+    #[test]
+    fn test_builds_scheme() {
+        let builds_scheme = |href: &str| {
+            let item = parse(format!("<template><a href=\"{}\"></a></template>", href), "Card".to_string(), "").unwrap();
+
+            match &item.children[0] {
+                Node::Element(e) => match &e.attrs[0] {
+                    Attribute::Normal(n) => n.value.builds_scheme(),
+                    attr => panic!("expected a normal attribute, found {:?}", attr),
+                },
+                node => panic!("expected an element, found {:?}", node),
+            }
+        };
+
+        assert!(builds_scheme("{{ scheme }}:{{ rest }}"));
+        assert!(builds_scheme("java{{ x }}"));
+        assert!(builds_scheme("java{{ x }}/a"));
+        assert!(!builds_scheme("{{ url }}"));
+        assert!(!builds_scheme(" {{ url }}"));
+        assert!(!builds_scheme("{{ base }}/users/{{ id }}"));
+        assert!(!builds_scheme("https://{{ host }}:{{ port }}"));
+        assert!(!builds_scheme("/users/{{ id }}?q={{ q }}"));
+        assert!(!builds_scheme("javascript:void(0)"));
+    }
+
+    #[test]
+    fn test_parse_filters() {
+        let item = parse(
+            "<template>{{ a | b(1, c) | d }}{{ (a | b) }}{{ a || b }}</template>".to_string(),
+            "Card".to_string(),
+            "",
+        )
+        .unwrap();
+
+        let parts = match &item.children[0] {
+            Node::Text(text) => &text.content,
+            node => panic!("expected text, found {:?}", node),
+        };
+
+        match &parts[0] {
+            StringPart::Expr(Code::Expr(Expr::Filter(d))) => {
+                assert_eq!(d.name, "d");
+                assert!(d.args.is_empty());
+
+                match &*d.expr {
+                    Expr::Filter(b) => {
+                        assert_eq!(b.name, "b");
+                        assert_eq!(b.args.len(), 2);
+                        assert!(matches!(&*b.expr, Expr::Path(_)));
+                    }
+                    _ => panic!("expected the `b` filter"),
+                }
+            }
+            part => panic!("expected filters, found {:?}", part),
+        }
+
+        assert!(matches!(&parts[1], StringPart::Expr(Code::Expr(Expr::Paren(_)))));
+        assert!(matches!(&parts[2], StringPart::Expr(Code::Expr(Expr::Binary(_)))));
+
+        let err = parse("<template>{{ a | }}</template>".to_string(), "Card".to_string(), "").unwrap_err();
+
+        assert_eq!(err.message, "expected expression");
+    }
+
+    #[test]
+    fn test_parse_trim_markers() {
+        let item = parse(
+            "<template><p title=\"a  {{- b }} c\">\n  {{ a -}}\n  b {{- -c -}} </p></template>".to_string(),
+            "Card".to_string(),
+            "",
+        )
+        .unwrap();
+
+        let p = match &item.children[0] {
+            Node::Element(p) => p,
+            node => panic!("expected `p`, found {:?}", node),
+        };
+        let parts = match &p.children[0] {
+            Node::Text(text) => &text.content,
+            node => panic!("expected text, found {:?}", node),
+        };
+
+        assert!(matches!(&parts[0], StringPart::Normal(text) if text == "\n  "));
+        assert!(matches!(&parts[1], StringPart::Expr(Code::Expr(Expr::Path(_)))));
+        assert!(matches!(&parts[2], StringPart::Normal(text) if text == "b"));
+        assert!(matches!(&parts[3], StringPart::Expr(Code::Expr(Expr::Unary(_)))));
+        assert_eq!(parts.len(), 4);
+
+        match &p.attrs[0] {
+            Attribute::Normal(attr) => {
+                assert!(matches!(&attr.value.parts[0], StringPart::Normal(text) if text == "a"));
+                assert!(matches!(&attr.value.parts[2], StringPart::Normal(text) if text == " c"));
+            }
+            attr => panic!("expected normal attribute, found {:?}", attr),
+        }
+    }
+
+    #[test]
+    fn test_parse_template_comments() {
+        let item = parse(
+            "<template><p>{{# <b>{{ a }}</b> #}}</p>a{{#b#}} {{ c }}<!-- d --></template>".to_string(),
+            "Card".to_string(),
+            "",
+        )
+        .unwrap();
+
+        assert_eq!(item.children.len(), 3);
+
+        match &item.children[0] {
+            Node::Element(p) => assert!(matches!(&p.children[..], [Node::Text(text)] if text.content.is_empty())),
+            node => panic!("expected `p`, found {:?}", node),
+        }
+
+        match &item.children[1] {
+            Node::Text(text) => {
+                assert!(matches!(&text.content[0], StringPart::Normal(text) if text == "a"));
+                assert!(matches!(&text.content[1], StringPart::Normal(text) if text == " "));
+                assert_eq!(text.content.len(), 3);
+            }
+            node => panic!("expected text, found {:?}", node),
+        }
+
+        let err = parse("<template>{{# a }}</template>".to_string(), "Card".to_string(), "").unwrap_err();
+
+        assert_eq!(err.message, "comment incomplete");
+    }
+
+    #[test]
+    fn test_parse_case() {
+        let item = parse(
+            "<template><p !match=\"x\"><b !case=\"Some(1) | None\"></b><i !case=\"Some(n) if *n > 1\"></i></p></template>"
+                .to_string(),
+            "Card".to_string(),
+            "",
+        )
+        .unwrap();
+
+        assert_eq!(item.children.len(), 1);
+
+        let err = parse("<template><b !case=\"1 +\"></b></template>".to_string(), "Card".to_string(), "")
+            .unwrap_err();
+
+        assert_eq!(err.message, "expected pattern in `!case`");
+    }
+
+    #[test]
+    fn test_parse_if_let() {
+        let item = parse(
+            "<template><b !if=\"let Some(a) = b\"></b><i !else-if=\"letter\"></i></template>".to_string(),
+            "Card".to_string(),
+            "",
+        )
+        .unwrap();
+
+        assert_eq!(item.children.len(), 2);
+
+        let err = parse("<template><b !if=\"let Some(a)\"></b></template>".to_string(), "Card".to_string(), "")
+            .unwrap_err();
+
+        assert_eq!(err.message, "expected `let pattern = expression`");
+    }
+}
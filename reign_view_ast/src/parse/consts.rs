@@ -2,13 +2,30 @@ pub const DOCTYPE: &str = "(?i)<!DOCTYPE [^>]+>";
 pub const TAG_NAME: &str = "[[:alpha:]](:?[a-zA-Z0-9\\-]*[[:alnum:]])?";
 pub const ATTR_NAME: &str = "[^\\s\"\'>/=]+";
 pub const VAR_ATTR_SYMBOL: &str = ":";
-pub const CTRL_ATTR: &str = "!(for|if|else-if|else)";
+pub const CTRL_ATTR: &str = "!(inherit-attrs|for|if|else-if|else|match|case|empty)";
 pub const DY_ATTR_NAME_PART: &str = "[^\\{}\\s\"\'>/=]*";
 pub const DY_ATTR_EXPR: &str = "\\{\\{([^=]+)}}";
 pub const ATTR_VALUE_DOUBLE_QUOTED: &str = "\"([^\"]*)\"";
 pub const ATTR_VALUE_SINGLE_QUOTED: &str = "'([^']*)'";
 pub const ATTR_VALUE_UNQUOTED: &str = "[^\\s\"'=<>`]+";
 
+/// Named character references decoded by `decode_references`, the same as
+/// the ones `reign::view::escape` decodes when checking the scheme of URLs.
+pub const NAMED_REFERENCES: [(&str, char); 12] = [
+    ("Tab", '\t'),
+    ("NewLine", '\n'),
+    ("colon", ':'),
+    ("sol", '/'),
+    ("quest", '?'),
+    ("num", '#'),
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{A0}'),
+];
+
 pub const HTML_TAGS: [&str; 116] = [
     "html",
     "body",
@@ -165,3 +182,19 @@ pub const SVG_TAGS: [&str; 30] = [
     "use",
     "view",
 ];
+
+// Attributes whose value is a URL
+pub const URL_ATTRIBUTES: [&str; 12] = [
+    "href",
+    "src",
+    "action",
+    "formaction",
+    "cite",
+    "poster",
+    "background",
+    "data",
+    "codebase",
+    "longdesc",
+    "manifest",
+    "xlink:href",
+];
@@ -1,13 +1,24 @@
 use std::fmt;
 
-// TODO:(view:err) Filename for error
 pub struct Error {
     pub content: String,
     pub cursor: usize,
     pub message: String,
+    /// Path of the template file, shown before the snippet when set
+    pub filename: Option<String>,
 }
 
 impl Error {
+    /// Error at the given position of a template file
+    pub fn new(filename: String, content: String, cursor: usize, message: String) -> Self {
+        Error {
+            content,
+            cursor,
+            message,
+            filename: Some(filename),
+        }
+    }
+
     fn get_line(&self) -> (usize, usize, String) {
         if self.cursor > self.content.len() {
             panic!(
@@ -76,6 +87,20 @@ impl fmt::Debug for Error {
     }
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (column, line, _) = self.get_line();
+
+        write!(f, "{}", self.message)?;
+
+        if let Some(filename) = &self.filename {
+            write!(f, "\n --> {}:{}:{}", filename, line, column + 1)?;
+        }
+
+        self.print(f)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::super::ParseStream;
@@ -213,6 +238,28 @@ mod test {
   |      |
   |      out of bounds when trying to find `!`
   |
+"
+        );
+    }
+
+    #[test]
+    fn test_display_filename() {
+        let mut ps = ParseStream::new("Hello\nWorld".to_string());
+        ps.step("Hello\nWo").unwrap();
+        let mut err = ps.step("!").unwrap_err();
+
+        err.filename = Some("src/views/hello.html".to_string());
+
+        assert_eq!(
+            format!("{}", err),
+            "expected `!`
+ --> src/views/hello.html:2:3
+  |
+2 | World
+  |   -^^^
+  |   |
+  |   expected `!`
+  |
 "
         );
     }
@@ -22,6 +22,7 @@ impl ParseStream {
             content: self.content.clone(),
             cursor: self.cursor,
             message: msg.to_string(),
+            filename: None,
         }
     }
 
@@ -160,7 +161,7 @@ mod test {
 
         assert_eq!(ps.cursor, 8);
         assert_eq!(parts.len(), 1);
-        assert!(if let Some(StringPart::Normal(s)) = parts.get(0) {
+        assert!(if let Some(StringPart::Normal(s)) = parts.first() {
             s == "Hello"
         } else {
             false
@@ -175,7 +176,7 @@ mod test {
 
         assert_eq!(ps.cursor, 4);
         assert_eq!(parts.len(), 1);
-        assert!(if let Some(StringPart::Normal(s)) = parts.get(0) {
+        assert!(if let Some(StringPart::Normal(s)) = parts.first() {
             s == "text"
         } else {
             false
@@ -190,7 +191,7 @@ mod test {
 
         assert_eq!(ps.cursor, 11);
         assert_eq!(parts.len(), 2);
-        assert!(if let Some(StringPart::Normal(s)) = parts.get(0) {
+        assert!(if let Some(StringPart::Normal(s)) = parts.first() {
             s == "\\{{"
         } else {
             false
@@ -211,7 +212,7 @@ mod test {
         assert_eq!(ps.cursor, 14);
         assert_eq!(parts.len(), 2);
         assert!(
-            if let Some(StringPart::Expr(Code::Expr(Expr::Path(p)))) = parts.get(0) {
+            if let Some(StringPart::Expr(Code::Expr(Expr::Path(p)))) = parts.first() {
                 p.path.is_ident(&Ident::new("text", Span::call_site()))
             } else {
                 false
@@ -0,0 +1,536 @@
+use super::{parse_element_attrs, Attribute, Error, ParseStream};
+
+// Escapes like `\:` or `\31 ` are allowed anywhere in names, e.g. `.sm\:p-4`
+const IDENT: &str = r"-{0,2}(?:[_a-zA-Z\x{80}-\x{10FFFF}]|\\[0-9a-fA-F]{1,6}\s?|\\[^\n\r0-9a-fA-F])(?:[-_a-zA-Z0-9\x{80}-\x{10FFFF}]|\\[0-9a-fA-F]{1,6}\s?|\\[^\n\r0-9a-fA-F])*";
+const NAME: &str = r"(?:[-_a-zA-Z0-9\x{80}-\x{10FFFF}]|\\[0-9a-fA-F]{1,6}\s?|\\[^\n\r0-9a-fA-F])+";
+
+// At-rules whose block contains rules instead of declarations
+const GROUP_RULES: [&str; 6] = ["media", "supports", "container", "layer", "document", "-moz-document"];
+
+// Pseudo-elements which can still be written with a single colon
+const LEGACY_PSEUDO_ELEMENTS: [&str; 4] = ["before", "after", "first-line", "first-letter"];
+
+/// Contents of a `<style>` element.
+#[derive(Debug)]
+pub struct Style {
+    /// Whether the element has the `scoped` attribute
+    pub scoped: bool,
+    /// Stylesheet as written in the template file
+    pub source: String,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+enum Rule {
+    Style {
+        selectors: Vec<Selector>,
+        declarations: String,
+    },
+    At {
+        name: String,
+        prelude: String,
+        block: AtBlock,
+    },
+}
+
+#[derive(Debug)]
+enum AtBlock {
+    None,
+    Rules(Vec<Rule>),
+    Raw(String),
+}
+
+/// Compound selectors along with the combinator before them.
+#[derive(Debug)]
+struct Selector(Vec<(Option<char>, Vec<Simple>)>);
+
+#[derive(Debug)]
+enum Simple {
+    /// Type, class, id, attribute and pseudo-class selectors
+    Plain(String),
+    PseudoElement(String),
+    /// Contents of `:global(...)`, never scoped
+    Global(String),
+}
+
+/// Parses a `<style>` element once its tag name has been consumed.
+pub fn parse_style(input: &mut ParseStream) -> Result<Style, Error> {
+    let mut scoped = false;
+
+    for attr in parse_element_attrs(input)? {
+        match attr {
+            Attribute::Normal(n) if n.name == "scoped" => scoped = true,
+            _ => return Err(input.error("Only the 'scoped' attribute is supported on 'style' elements")),
+        }
+    }
+
+    if input.peek("/>") {
+        return Err(input.error("Self closing style tags unsupported (sorry)"));
+    }
+
+    input.step(">")?;
+
+    // Only scoped styles are rewritten, the others are kept as they are written
+    let (source, rules) = if scoped {
+        let start = input.cursor;
+        let rules = parse_rules(input, "</style")?;
+
+        (input.content[start..input.cursor].to_string(), rules)
+    } else {
+        (input.until("</style", false)?, vec![])
+    };
+
+    input.step("</style")?;
+    input.skip_spaces()?;
+    input.step(">")?;
+
+    Ok(Style { scoped, source, rules })
+}
+
+/// Attribute added to the elements of the templates in a file with scoped
+/// styles. It only depends on the module and name of the file and on the scoped
+/// styles so that it doesn't change when the rest of the template is edited.
+pub fn scope_attribute(module: &str, name: &str, styles: &[Style]) -> Option<String> {
+    if !styles.iter().any(|style| style.scoped) {
+        return None;
+    }
+
+    // FNV-1a, which is stable across compilers unlike `DefaultHasher`
+    let mut hash: u32 = 0x811c_9dc5;
+    let sources = styles.iter().filter(|style| style.scoped).map(|style| style.source.as_str());
+
+    for chunk in [module, name].iter().copied().chain(sources) {
+        for byte in chunk.bytes().chain(std::iter::once(0)) {
+            hash = (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193);
+        }
+    }
+
+    Some(format!("data-r-{:08x}", hash))
+}
+
+impl Style {
+    /// Prints the stylesheet, adding the scope attribute to the selectors if it is scoped.
+    pub fn to_css(&self, scope: Option<&str>) -> String {
+        if !self.scoped {
+            return format!("{}\n", self.source.trim());
+        }
+
+        let mut out = String::new();
+
+        write_rules(&self.rules, scope, &mut out);
+        out
+    }
+}
+
+fn parse_rules(input: &mut ParseStream, end: &str) -> Result<Vec<Rule>, Error> {
+    let mut rules = vec![];
+
+    loop {
+        skip_trivia(input)?;
+
+        if input.peek(end) {
+            return Ok(rules);
+        }
+
+        if input.cursor == input.content.len() {
+            return Err(input.error(&format!("expected `{}`", end)));
+        }
+
+        rules.push(if input.peek("@") {
+            parse_at_rule(input)?
+        } else {
+            parse_style_rule(input)?
+        });
+    }
+}
+
+fn parse_at_rule(input: &mut ParseStream) -> Result<Rule, Error> {
+    input.step("@")?;
+
+    let name = match input.matched(IDENT) {
+        Ok(name) => name,
+        Err(_) => return Err(input.error("expected at-rule name")),
+    };
+    let prelude = raw_until(input, &['{', ';'])?.trim().to_string();
+
+    let block = if input.peek(";") {
+        AtBlock::None
+    } else if GROUP_RULES.contains(&name.to_lowercase().as_str()) {
+        input.step("{")?;
+        AtBlock::Rules(parse_rules(input, "}")?)
+    } else {
+        input.step("{")?;
+        AtBlock::Raw(raw_until(input, &['}'])?)
+    };
+
+    if input.peek(";") {
+        input.step(";")?;
+    } else {
+        input.step("}")?;
+    }
+
+    Ok(Rule::At { name, prelude, block })
+}
+
+fn parse_style_rule(input: &mut ParseStream) -> Result<Rule, Error> {
+    let mut selectors = vec![];
+
+    loop {
+        skip_trivia(input)?;
+        selectors.push(parse_selector(input)?);
+
+        if input.peek(",") {
+            input.step(",")?;
+        } else if input.peek("{") {
+            break;
+        } else {
+            return Err(input.error("expected `,` or `{` after selector"));
+        }
+    }
+
+    input.step("{")?;
+    let declarations = raw_until(input, &['}'])?;
+    input.step("}")?;
+
+    Ok(Rule::Style { selectors, declarations })
+}
+
+fn parse_selector(input: &mut ParseStream) -> Result<Selector, Error> {
+    let mut compounds = vec![];
+    let mut combinator = None;
+
+    loop {
+        let compound = parse_compound(input)?;
+
+        if compound.is_empty() {
+            return Err(input.error("expected selector"));
+        }
+
+        compounds.push((combinator, compound));
+
+        let cursor = input.cursor;
+        skip_trivia(input)?;
+
+        combinator = ['>', '+', '~']
+            .iter()
+            .copied()
+            .find(|c| input.content[input.cursor..].starts_with(*c));
+
+        if let Some(c) = combinator {
+            input.step(&c.to_string())?;
+            skip_trivia(input)?;
+        } else if input.peek(",") || input.peek("{") {
+            return Ok(Selector(compounds));
+        } else if input.cursor != cursor {
+            combinator = Some(' ');
+        } else {
+            return Err(input.error("unexpected character in selector"));
+        }
+    }
+}
+
+fn parse_compound(input: &mut ParseStream) -> Result<Vec<Simple>, Error> {
+    let mut parts = vec![];
+
+    loop {
+        let part = if input.peek("*") || input.peek("&") {
+            Simple::Plain(input.matched("[*&]")?)
+        } else if input.peek(".") {
+            input.step(".")?;
+            Simple::Plain(format!(".{}", input.matched(IDENT)?))
+        } else if input.peek("#") {
+            input.step("#")?;
+            Simple::Plain(format!("#{}", input.matched(NAME)?))
+        } else if input.peek("[") {
+            input.step("[")?;
+            let attr = raw_until(input, &[']'])?;
+            input.step("]")?;
+            Simple::Plain(format!("[{}]", attr.trim()))
+        } else if input.peek("::") {
+            input.step("::")?;
+            Simple::PseudoElement(format!("::{}{}", input.matched(IDENT)?, parse_args(input)?))
+        } else if input.peek(":") {
+            input.step(":")?;
+            let name = input.matched(IDENT)?;
+
+            if name == "global" {
+                input.step("(")?;
+                let selector = raw_until(input, &[')'])?;
+                input.step(")")?;
+                Simple::Global(selector.trim().to_string())
+            } else if LEGACY_PSEUDO_ELEMENTS.contains(&name.as_str()) {
+                Simple::PseudoElement(format!(":{}", name))
+            } else {
+                Simple::Plain(format!(":{}{}", name, parse_args(input)?))
+            }
+        } else if input.is_match(IDENT) {
+            Simple::Plain(input.matched(IDENT)?)
+        } else {
+            return Ok(parts);
+        };
+
+        parts.push(part);
+    }
+}
+
+// Arguments of a functional pseudo-class like `:not(...)`, if any
+fn parse_args(input: &mut ParseStream) -> Result<String, Error> {
+    if !input.peek("(") {
+        return Ok(String::new());
+    }
+
+    input.step("(")?;
+    let args = raw_until(input, &[')'])?;
+    input.step(")")?;
+
+    Ok(format!("({})", args))
+}
+
+fn skip_trivia(input: &mut ParseStream) -> Result<(), Error> {
+    loop {
+        input.skip_spaces()?;
+
+        if !input.peek("/*") {
+            return Ok(());
+        }
+
+        input.step("/*")?;
+        input.until("*/", true)?;
+    }
+}
+
+// Consumes everything until one of `stops` outside of any brackets,
+// strings or comments, leaving the cursor on it.
+fn raw_until(input: &mut ParseStream, stops: &[char]) -> Result<String, Error> {
+    let start = input.cursor;
+    let (mut depth, mut string, mut comment) = (0, None, false);
+    let mut end = None;
+    let mut chars = input.content[start..].char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if comment {
+            if c == '*' && chars.peek().map(|x| x.1) == Some('/') {
+                chars.next();
+                comment = false;
+            }
+
+            continue;
+        }
+
+        if let Some(quote) = string {
+            if c == '\\' {
+                chars.next();
+            } else if c == quote {
+                string = None;
+            }
+
+            continue;
+        }
+
+        match c {
+            _ if depth == 0 && stops.contains(&c) => {
+                end = Some(start + i);
+                break;
+            }
+            '"' | '\'' => string = Some(c),
+            '\\' => {
+                chars.next();
+            }
+            '/' if chars.peek().map(|x| x.1) == Some('*') => {
+                chars.next();
+                comment = true;
+            }
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    match end {
+        Some(end) => {
+            input.cursor = end;
+            Ok(input.content[start..end].to_string())
+        }
+        None => {
+            input.cursor = input.content.len();
+            Err(input.error(&format!("expected `{}`", stops[0])))
+        }
+    }
+}
+
+fn write_rules(rules: &[Rule], scope: Option<&str>, out: &mut String) {
+    for rule in rules {
+        match rule {
+            Rule::Style { selectors, declarations } => {
+                for (i, selector) in selectors.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+
+                    selector.write(scope, out);
+                }
+
+                out.push_str(" {");
+                out.push_str(declarations.trim());
+                out.push_str("}\n");
+            }
+            Rule::At { name, prelude, block } => {
+                out.push('@');
+                out.push_str(name);
+
+                if !prelude.is_empty() {
+                    out.push(' ');
+                    out.push_str(prelude);
+                }
+
+                match block {
+                    AtBlock::None => out.push_str(";\n"),
+                    AtBlock::Rules(rules) => {
+                        out.push_str(" {\n");
+                        write_rules(rules, scope, out);
+                        out.push_str("}\n");
+                    }
+                    AtBlock::Raw(raw) => {
+                        out.push_str(" {");
+                        out.push_str(raw.trim());
+                        out.push_str("}\n");
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Selector {
+    // The scope attribute goes on the last compound selector which is not global
+    fn write(&self, scope: Option<&str>, out: &mut String) {
+        let target = scope.and_then(|_| {
+            self.0
+                .iter()
+                .rposition(|(_, parts)| !parts.iter().any(|part| matches!(part, Simple::Global(_))))
+        });
+
+        for (i, (combinator, parts)) in self.0.iter().enumerate() {
+            match combinator {
+                Some(' ') => out.push(' '),
+                Some(c) => {
+                    out.push(' ');
+                    out.push(*c);
+                    out.push(' ');
+                }
+                None => {}
+            }
+
+            let mut scoped = target != Some(i);
+
+            for part in parts {
+                if !scoped && matches!(part, Simple::PseudoElement(_)) {
+                    write_scope(scope, out);
+                    scoped = true;
+                }
+
+                match part {
+                    Simple::Plain(s) | Simple::PseudoElement(s) | Simple::Global(s) => out.push_str(s),
+                }
+            }
+
+            if !scoped {
+                write_scope(scope, out);
+            }
+        }
+    }
+}
+
+fn write_scope(scope: Option<&str>, out: &mut String) {
+    if let Some(scope) = scope {
+        out.push('[');
+        out.push_str(scope);
+        out.push(']');
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn scoped(css: &str) -> Result<String, Error> {
+        let mut input = ParseStream::new(format!(" scoped>{}</style>", css));
+        let style = parse_style(&mut input)?;
+
+        Ok(style.to_css(Some("data-r-1")))
+    }
+
+    #[test]
+    fn test_selectors() {
+        assert_eq!(
+            scoped("h1, .a > p.b:hover, ul li::before { color: red; }").unwrap(),
+            "h1[data-r-1], .a > p.b:hover[data-r-1], ul li[data-r-1]::before {color: red;}\n"
+        );
+        assert_eq!(
+            scoped("a[href^='{x}']:not(.c, .d) + *{}").unwrap(),
+            "a[href^='{x}']:not(.c, .d) + *[data-r-1] {}\n"
+        );
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_eq!(
+            scoped(".sm\\:p-4, .w-1\\/2:hover, #\\31 23 {}").unwrap(),
+            ".sm\\:p-4[data-r-1], .w-1\\/2:hover[data-r-1], #\\31 23[data-r-1] {}\n"
+        );
+    }
+
+    #[test]
+    fn test_global() {
+        assert_eq!(
+            scoped(".a :global(.b .c) { margin: 0 } :global(body) {}").unwrap(),
+            ".a[data-r-1] .b .c {margin: 0}\nbody {}\n"
+        );
+    }
+
+    #[test]
+    fn test_at_rules() {
+        assert_eq!(
+            scoped("@import 'a.css'; /* x { */ @media (max-width: 600px) { .a { b: c } } @keyframes spin { from { x: 0 } to { x: 1 } }").unwrap(),
+            "@import 'a.css';\n@media (max-width: 600px) {\n.a[data-r-1] {b: c}\n}\n@keyframes spin {from { x: 0 } to { x: 1 }}\n"
+        );
+    }
+
+    #[test]
+    fn test_unscoped() {
+        let mut input = ParseStream::new(">p { a: b } @unknown-syntax }{ </style>".to_string());
+        let style = parse_style(&mut input).unwrap();
+
+        assert!(!style.scoped);
+        assert_eq!(style.to_css(Some("data-r-1")), "p { a: b } @unknown-syntax }{\n");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(scoped(".a { color: red;").is_err());
+        assert!(scoped(".a ) {}").is_err());
+        assert!(scoped("p {}").is_ok());
+
+        let mut input = ParseStream::new(" lang=\"scss\">p {}</style>".to_string());
+        assert!(parse_style(&mut input).is_err());
+    }
+
+    #[test]
+    fn test_scope_attribute() {
+        let style = |scoped: bool, source: &str| Style {
+            scoped,
+            source: source.to_string(),
+            rules: vec![],
+        };
+
+        assert_eq!(scope_attribute("", "Card", &[style(false, "p {}")]), None);
+
+        let card = scope_attribute("", "Card", &[style(true, "p {}")]).unwrap();
+
+        assert!(card.starts_with("data-r-"));
+        assert_eq!(Some(card.clone()), scope_attribute("", "Card", &[style(true, "p {}")]));
+        assert_ne!(Some(card.clone()), scope_attribute("", "Card", &[style(true, "a {}")]));
+        assert_ne!(Some(card.clone()), scope_attribute("", "Page", &[style(true, "p {}")]));
+        assert_ne!(Some(card), scope_attribute("shared", "Card", &[style(true, "p {}")]));
+    }
+}
@@ -1,7 +1,5 @@
 use super::super::is_member_named;
-use super::{Pat, PatIdent, Tokenize, ViewFields};
-use proc_macro2::TokenStream;
-use quote::ToTokens;
+use super::{Pat, PatIdent};
 use syn::{
     parse::{Parse, ParseStream, Result},
     token::{Colon, Ref},
@@ -44,14 +42,3 @@ impl Parse for FieldPat {
         })
     }
 }
-
-impl Tokenize for FieldPat {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        if let Some(colon_token) = &self.colon_token {
-            self.member.to_tokens(tokens);
-            colon_token.to_tokens(tokens);
-        }
-
-        self.pat.tokenize(tokens, idents, scopes);
-    }
-}
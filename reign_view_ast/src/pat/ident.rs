@@ -1,6 +1,3 @@
-use super::{Tokenize, ViewFields};
-use proc_macro2::TokenStream;
-use quote::ToTokens;
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseStream, Result},
@@ -21,11 +18,3 @@ impl Parse for PatIdent {
         })
     }
 }
-
-impl Tokenize for PatIdent {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, _: &ViewFields) {
-        self.by_ref.to_tokens(tokens);
-        self.ident.to_tokens(tokens);
-        idents.push(self.ident.clone());
-    }
-}
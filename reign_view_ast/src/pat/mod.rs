@@ -1,12 +1,10 @@
-use super::{expr::expr_no_struct, Expr, tokenize::Tokenize, tokenize::ViewFields};
-use proc_macro2::TokenStream;
-use quote::ToTokens;
+use super::{expr::expr_no_struct, Expr};
 use syn::{
     braced,
     parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
-    token::{Brace, Comma, Dot2, In, Paren},
-    Ident, PatRest, PatWild, Path, Token,
+    token::{Brace, Comma, Dot2, If, In, Paren},
+    Ident, Lit, PatRest, PatWild, Path, Token,
 };
 
 mod field_pat;
@@ -16,16 +14,20 @@ mod struct_;
 mod tuple;
 mod tuple_struct;
 
-use field_pat::FieldPat;
-use ident::PatIdent;
-use reference::PatReference;
-use struct_::PatStruct;
-use tuple::PatTuple;
-use tuple_struct::PatTupleStruct;
+pub use field_pat::FieldPat;
+pub use ident::PatIdent;
+pub use reference::PatReference;
+pub use struct_::PatStruct;
+pub use tuple::PatTuple;
+pub use tuple_struct::PatTupleStruct;
 
 // TODO:(view:pat) Slice, Range
 pub enum Pat {
     Ident(PatIdent),
+    /// Literal, optionally negated
+    Lit(Option<Token![-]>, Lit),
+    /// Unit struct or enum variant
+    Path(Path),
     Reference(PatReference),
     Rest(PatRest),
     Struct(PatStruct),
@@ -49,6 +51,8 @@ impl Parse for Pat {
             || input.peek(Token![crate])
         {
             pat_struct_or_tuple_struct(input)
+        } else if lookahead.peek(Lit) || lookahead.peek(Token![-]) {
+            Ok(Pat::Lit(input.parse()?, input.parse()?))
         } else if lookahead.peek(Token![_]) {
             Ok(Pat::Wild(PatWild {
                 attrs: Vec::new(),
@@ -71,37 +75,12 @@ impl Parse for Pat {
     }
 }
 
-impl Tokenize for Pat {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        match self {
-            Pat::Ident(p) => p.tokenize(tokens, idents, scopes),
-            Pat::Reference(p) => p.tokenize(tokens, idents, scopes),
-            Pat::Rest(p) => p.to_tokens(tokens),
-            Pat::Struct(p) => p.tokenize(tokens, idents, scopes),
-            Pat::TupleStruct(p) => p.tokenize(tokens, idents, scopes),
-            Pat::Tuple(p) => p.tokenize(tokens, idents, scopes),
-            Pat::Wild(p) => p.to_tokens(tokens),
-        };
-    }
-}
-
 pub struct For {
     pub pat: Pat,
     pub in_token: In,
     pub expr: Box<Expr>,
 }
 
-impl For {
-    pub fn declared(&self) -> ViewFields {
-        let mut declared = ViewFields::new();
-        let mut tokens = TokenStream::new();
-        let scopes = ViewFields::new();
-
-        self.pat.tokenize(&mut tokens, &mut declared, &scopes);
-        declared
-    }
-}
-
 impl Parse for For {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(For {
@@ -112,13 +91,53 @@ impl Parse for For {
     }
 }
 
-impl Tokenize for For {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        let mut declared = ViewFields::new();
+/// Condition of `!if` and `!else-if` which binds the matched pattern.
+pub struct Let {
+    pub let_token: Token![let],
+    pub pat: Pat,
+    pub eq_token: Token![=],
+    pub expr: Box<Expr>,
+}
+
+impl Parse for Let {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Let {
+            let_token: input.parse()?,
+            pat: input.parse()?,
+            eq_token: input.parse()?,
+            expr: Box::new(input.call(expr_no_struct)?),
+        })
+    }
+}
+
+/// Pattern of a `!case` element along with its guard.
+pub struct Case {
+    pub pats: Punctuated<Pat, Token![|]>,
+    pub guard: Option<(If, Box<Expr>)>,
+}
+
+impl Parse for Case {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let _leading_vert: Option<Token![|]> = input.parse()?;
+        let mut pats = Punctuated::new();
+
+        loop {
+            pats.push_value(input.parse()?);
+
+            if !input.peek(Token![|]) {
+                break;
+            }
+
+            pats.push_punct(input.parse()?);
+        }
+
+        let guard = if input.peek(Token![if]) {
+            Some((input.parse()?, Box::new(input.parse()?)))
+        } else {
+            None
+        };
 
-        self.pat.tokenize(tokens, &mut declared, scopes);
-        self.in_token.to_tokens(tokens);
-        self.expr.tokenize(tokens, idents, scopes);
+        Ok(Case { pats, guard })
     }
 }
 
@@ -132,7 +151,7 @@ fn pat_struct_or_tuple_struct(input: ParseStream) -> Result<Pat> {
     } else if input.peek(Paren) {
         pat_tuple_struct(input, path).map(Pat::TupleStruct)
     } else {
-        Err(input.error("expected struct or tuple struct"))
+        Ok(Pat::Path(path))
     }
 }
 
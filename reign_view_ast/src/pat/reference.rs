@@ -1,6 +1,4 @@
-use super::{Pat, Tokenize, ViewFields};
-use proc_macro2::TokenStream;
-use quote::ToTokens;
+use super::Pat;
 use syn::{
     parse::{Parse, ParseStream, Result},
     token::And,
@@ -19,10 +17,3 @@ impl Parse for PatReference {
         })
     }
 }
-
-impl Tokenize for PatReference {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.and_token.to_tokens(tokens);
-        self.pat.tokenize(tokens, idents, scopes);
-    }
-}
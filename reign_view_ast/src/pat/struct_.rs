@@ -0,0 +1,13 @@
+use super::FieldPat;
+use syn::{
+    punctuated::Punctuated,
+    token::{Brace, Comma, Dot2},
+    Path,
+};
+
+pub struct PatStruct {
+    pub path: Path,
+    pub brace_token: Brace,
+    pub fields: Punctuated<FieldPat, Comma>,
+    pub dot2_token: Option<Dot2>,
+}
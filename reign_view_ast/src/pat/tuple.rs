@@ -1,5 +1,4 @@
-use super::{Pat, Tokenize, ViewFields};
-use proc_macro2::TokenStream;
+use super::Pat;
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream, Result},
@@ -34,11 +33,3 @@ impl Parse for PatTuple {
         Ok(PatTuple { paren_token, elems })
     }
 }
-
-impl Tokenize for PatTuple {
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        self.paren_token.surround(tokens, |tokens| {
-            self.elems.tokenize(tokens, idents, scopes);
-        });
-    }
-}
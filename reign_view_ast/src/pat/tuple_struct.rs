@@ -0,0 +1,7 @@
+use super::PatTuple;
+use syn::Path;
+
+pub struct PatTupleStruct {
+    pub path: Path,
+    pub pat: PatTuple,
+}
@@ -1 +1,4 @@
 pub use reign_derive::*;
+
+#[cfg(feature = "view")]
+pub use reign_view::{Render, Safe};